@@ -0,0 +1,339 @@
+//! Helpers for arrays of [`CTOption`]s sharing the same typestate.
+//!
+//! The module also provides [`with_capacity_markers`] for the all-none
+//! initial state of builders, which extends [`none_array`] to tuples.
+
+use core::mem::{ManuallyDrop, MaybeUninit};
+
+use crate::{erased::CtOptionDyn, CTNone, CTOption};
+
+/// Creates an array of `N` [`CTNone`]s.
+///
+/// `T` is not required to be [`Copy`].
+///
+/// ```
+/// use ctoption::{array::none_array, prelude::*};
+///
+/// const FIELDS: [CTNone<i32>; 2] = none_array();
+///
+/// let [a, b] = FIELDS;
+/// assert!(a.insert(1).into_inner() + b.insert(2).into_inner() == 3);
+/// ```
+pub const fn none_array<T, const N: usize>() -> [CTNone<T>; N] {
+    [const { CTNone::new() }; N]
+}
+
+/// Collections of [`CTNone`]s that have a single all-none value.
+///
+/// It is implemented for arrays of [`CTNone`]s and for tuples of up to 8 [`CTNone`]s,
+/// whose payload types may differ.
+pub trait AllNone {
+    /// The all-none value.
+    const ALL_NONE: Self;
+}
+
+impl<T, const N: usize> AllNone for [CTNone<T>; N] {
+    const ALL_NONE: Self = none_array();
+}
+
+macro_rules! impl_all_none_for_tuples {
+    ($(($($t:ident),+))+) => {
+        $(
+            impl<$($t),+> AllNone for ($(CTNone<$t>,)+) {
+                const ALL_NONE: Self = ($(CTNone::<$t>::new(),)+);
+            }
+        )+
+    };
+}
+
+impl_all_none_for_tuples! {
+    (A)
+    (A, B)
+    (A, B, C)
+    (A, B, C, D)
+    (A, B, C, D, E)
+    (A, B, C, D, E, F)
+    (A, B, C, D, E, F, G)
+    (A, B, C, D, E, F, G, H)
+}
+
+/// Creates the all-none initial state of a builder, e.g. a tuple of its fields.
+///
+/// ```
+/// use ctoption::{array::with_capacity_markers, prelude::*};
+///
+/// let (name, age): (CTNone<&str>, CTNone<u8>) = with_capacity_markers();
+/// assert!(name.insert("Ferris").into_inner() == "Ferris");
+/// assert!(age.insert(9).into_inner() == 9);
+/// ```
+pub const fn with_capacity_markers<S: AllNone>() -> S {
+    S::ALL_NONE
+}
+
+/// Converts an array of options sharing the flag `IS_SOME_VAL` into an optional array.
+///
+/// Returns `Some` with all the values if the options are [`CTSome`](crate::CTSome)
+/// and `None` otherwise.
+///
+/// ```
+/// use ctoption::{array::split_at_flag, prelude::*};
+///
+/// let values = split_at_flag([CTSome::new(1), CTSome::new(2)]);
+/// assert!(values == Some([1, 2]));
+/// ```
+///
+/// Since the flag is a part of the type, arrays with mixed typestates do not typecheck:
+///
+/// ```compile_fail
+/// use ctoption::{array::split_at_flag, prelude::*};
+///
+/// let values = split_at_flag([CTSome::new(1), CTNone::new()]);
+/// ```
+pub fn split_at_flag<T, const IS_SOME_VAL: bool, const N: usize>(
+    arr: [CTOption<T, IS_SOME_VAL>; N],
+) -> Option<[T; N]> {
+    partition_by_flag(arr).ok()
+}
+
+/// Partitions an array of options sharing the flag `IS_SOME_VAL` in a `const` context.
+///
+/// Since all the options share the typestate, the partition is all-or-nothing:
+/// `Ok` with all the values if they are [`CTSome`](crate::CTSome)s and `Err` with
+/// the number of [`CTNone`]s, i.e. `N`, otherwise. Arrays with mixed typestates
+/// can't be expressed and need a runtime path instead.
+///
+/// ```
+/// use ctoption::{array::partition_by_flag, prelude::*};
+///
+/// const VALUES: Result<[i32; 2], usize> = partition_by_flag([CTSome::new(1), CTSome::new(2)]);
+/// assert!(VALUES == Ok([1, 2]));
+/// ```
+pub const fn partition_by_flag<T, const IS_SOME_VAL: bool, const N: usize>(
+    arr: [CTOption<T, IS_SOME_VAL>; N],
+) -> Result<[T; N], usize> {
+    union CTOptionArrayUnion<U, const NESTED_IS_SOME_VAL: bool, const M: usize> {
+        md_ctopts: ManuallyDrop<[CTOption<U, NESTED_IS_SOME_VAL>; M]>,
+        md_inners: ManuallyDrop<[U; M]>,
+    }
+
+    let md_ctopts = ManuallyDrop::new(arr);
+    if IS_SOME_VAL {
+        let u = CTOptionArrayUnion { md_ctopts };
+        let md_inners = unsafe { u.md_inners };
+        Ok(ManuallyDrop::into_inner(md_inners))
+    } else {
+        Err(N)
+    }
+}
+
+/// Reshapes an array of options sharing the flag `IS_SOME_VAL` into chunks of `CHUNK` values
+/// in a `const` context.
+///
+/// Like [`partition_by_flag`], returns `Ok` with the values if the options are
+/// [`CTSome`](crate::CTSome)s and `Err` with the number of [`CTNone`]s otherwise.
+///
+/// # Panics
+///
+/// Panics if `N` is not a multiple of `CHUNK`. In a const context, this is a compile-time error.
+///
+/// ```
+/// #![allow(incomplete_features)]
+/// #![feature(generic_const_exprs)]
+///
+/// use ctoption::{array::chunk_options, prelude::*};
+///
+/// const ROWS: Result<[[i32; 2]; 2], usize> = chunk_options::<_, _, 4, 2>([
+///     CTSome::new(1),
+///     CTSome::new(2),
+///     CTSome::new(3),
+///     CTSome::new(4),
+/// ]);
+/// assert!(ROWS == Ok([[1, 2], [3, 4]]));
+/// ```
+#[cfg(feature = "generic_const_exprs")]
+#[track_caller]
+pub const fn chunk_options<T, const IS_SOME_VAL: bool, const N: usize, const CHUNK: usize>(
+    arr: [CTOption<T, IS_SOME_VAL>; N],
+) -> Result<[[T; CHUNK]; N / CHUNK], usize>
+where
+    [(); N / CHUNK]:,
+{
+    union CTOptionChunksUnion<
+        U,
+        const NESTED_IS_SOME_VAL: bool,
+        const M: usize,
+        const C: usize,
+        const R: usize,
+    > {
+        md_ctopts: ManuallyDrop<[CTOption<U, NESTED_IS_SOME_VAL>; M]>,
+        md_chunks: ManuallyDrop<[[U; C]; R]>,
+    }
+
+    assert!(
+        N.is_multiple_of(CHUNK),
+        "the array length isn't a multiple of the chunk length"
+    );
+    let md_ctopts = ManuallyDrop::new(arr);
+    if IS_SOME_VAL {
+        let u = CTOptionChunksUnion::<T, IS_SOME_VAL, N, CHUNK, { N / CHUNK }> { md_ctopts };
+        let md_chunks = unsafe { u.md_chunks };
+        Ok(ManuallyDrop::into_inner(md_chunks))
+    } else {
+        Err(N)
+    }
+}
+
+/// Collects the set values among `N` fields into an array of length `SET`, preserving the order.
+///
+/// Each field is given as a [`CtOptionDyn`], e.g. from [`CTOption::tag_erase`], which erases
+/// the differences in typestate between the fields. With `generic_const_exprs`, `SET` can be
+/// computed from the flags of the fields, e.g. with [`count_set!`](crate::count_set).
+///
+/// # Panics
+///
+/// Panics if the number of set fields is not `SET`, in which case the values are leaked.
+/// In a const context, this is a compile-time error.
+///
+/// ```
+/// use ctoption::{array::to_array, prelude::*};
+///
+/// const ARR: [i32; 2] = to_array([
+///     CTSome::new(1).tag_erase(),
+///     CTNone::new().tag_erase(),
+///     CTSome::new(3).tag_erase(),
+/// ]);
+/// assert!(ARR == [1, 3]);
+/// ```
+#[track_caller]
+pub const fn to_array<T, const N: usize, const SET: usize>(
+    fields: [CtOptionDyn<T>; N],
+) -> [T; SET] {
+    union MaybeUninitArrayUnion<U, const M: usize> {
+        md_uninits: ManuallyDrop<[MaybeUninit<U>; M]>,
+        md_inners: ManuallyDrop<[U; M]>,
+    }
+
+    let fields = ManuallyDrop::new(fields);
+    let fields = &fields as *const ManuallyDrop<[CtOptionDyn<T>; N]> as *const [CtOptionDyn<T>; N];
+    let fields = unsafe { &*fields };
+
+    let mut count = 0;
+    let mut i = 0;
+    while i < N {
+        count += fields[i].is_some as usize;
+        i += 1;
+    }
+    assert!(
+        count == SET,
+        "the number of set fields doesn't match the array length"
+    );
+
+    let mut uninits = [const { MaybeUninit::uninit() }; SET];
+    let mut i = 0;
+    let mut j = 0;
+    while i < N {
+        if fields[i].is_some {
+            uninits[j] = unsafe { core::ptr::read(&fields[i].storage) };
+            j += 1;
+        }
+        i += 1;
+    }
+
+    let u = MaybeUninitArrayUnion {
+        md_uninits: ManuallyDrop::new(uninits),
+    };
+    let md_inners = unsafe { u.md_inners };
+    ManuallyDrop::into_inner(md_inners)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn none_array_yields_usable_nones() {
+        let nones = none_array::<i32, 5>();
+        assert!(nones.iter().all(|none| !none.is_some()));
+
+        let mut sum = 0;
+        for (i, none) in nones.into_iter().enumerate() {
+            sum += none.insert(i as i32).into_inner();
+        }
+        assert!(sum == 10);
+    }
+
+    #[test]
+    fn with_capacity_markers_builds_all_none_state() {
+        type Fields = (
+            CTNone<i32>,
+            CTNone<u8>,
+            CTNone<bool>,
+            CTNone<char>,
+            CTNone<&'static str>,
+        );
+
+        let (a, b, c, d, e): Fields = with_capacity_markers();
+        assert!(!a.is_some() && !b.is_some() && !c.is_some() && !d.is_some() && !e.is_some());
+
+        let fields: [CTNone<i32>; 5] = with_capacity_markers();
+        assert!(fields.iter().all(|field| !field.is_some()));
+    }
+
+    #[cfg(feature = "generic_const_exprs")]
+    #[test]
+    fn chunk_options_reshapes_into_rows() {
+        const ROWS: Result<[[i32; 2]; 2], usize> = chunk_options::<_, _, 4, 2>([
+            CTSome::new(1),
+            CTSome::new(2),
+            CTSome::new(3),
+            CTSome::new(4),
+        ]);
+        assert!(ROWS == Ok([[1, 2], [3, 4]]));
+
+        let none = chunk_options::<i32, _, 4, 2>(none_array());
+        assert!(none == Err(4));
+    }
+
+    #[test]
+    fn to_array_collects_set_fields() {
+        let all: [i32; 5] = to_array([
+            CTSome::new(1).tag_erase(),
+            CTSome::new(2).tag_erase(),
+            CTSome::new(3).tag_erase(),
+            CTSome::new(4).tag_erase(),
+            CTSome::new(5).tag_erase(),
+        ]);
+        assert!(all == [1, 2, 3, 4, 5]);
+
+        let some: [i32; 2] = to_array([
+            CTNone::new().tag_erase(),
+            CTSome::new(2).tag_erase(),
+            CTNone::new().tag_erase(),
+            CTSome::new(4).tag_erase(),
+            CTNone::new().tag_erase(),
+        ]);
+        assert!(some == [2, 4]);
+
+        let none: [i32; 0] = to_array([const { CTNone::new().tag_erase() }; 5]);
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "doesn't match the array length")]
+    fn to_array_panics_on_mismatch() {
+        let _: [i32; 2] = to_array([CTSome::new(1).tag_erase(), CTNone::new().tag_erase()]);
+    }
+
+    #[test]
+    fn split_at_flag_extracts_all_some() {
+        let some = [CTSome::new(1), CTSome::new(2), CTSome::new(3)];
+        assert!(split_at_flag(some) == Some([1, 2, 3]));
+
+        let none = [CTNone::<i32>::new(), CTNone::new()];
+        assert!(split_at_flag(none).is_none());
+
+        let none = [CTNone::<i32>::new(), CTNone::new()];
+        assert!(partition_by_flag(none) == Err(2));
+    }
+}
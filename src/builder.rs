@@ -0,0 +1,222 @@
+//! A reusable typestate builder of arrays.
+//!
+//! [`ArrayBuilder`] packages the pattern where every field of a builder is a
+//! [`CTOption`](crate::CTOption) and [`build`](ArrayBuilder::build) collects the set
+//! ones into an array whose length is computed from the typestate. Instead of a
+//! const generic flag per field, the flags are the bits of a single `MASK`.
+//!
+//! ```
+//! #![allow(incomplete_features)]
+//! #![feature(generic_const_exprs)]
+//!
+//! use ctoption::builder::ArrayBuilder;
+//!
+//! let arr = ArrayBuilder::<i32, 3>::new().set::<2>(3).set::<0>(1).build();
+//! assert!(arr == [1, 3]);
+//! ```
+
+use core::mem::{ManuallyDrop, MaybeUninit};
+
+use crate::{array::to_array, erased::CtOptionDyn};
+
+/// A builder of an array of the values set at up to `N` indices, where the bits of
+/// `MASK` record which indices are set.
+///
+/// `N` can't exceed 64, i.e. the number of bits in `MASK`.
+pub struct ArrayBuilder<T, const N: usize, const MASK: u64 = 0> {
+    storage: [MaybeUninit<T>; N],
+}
+
+impl<T, const N: usize> ArrayBuilder<T, N> {
+    /// Creates a builder with no indices set.
+    ///
+    /// Creating a builder with more than 64 indices is a compile-time error:
+    ///
+    /// ```compile_fail
+    /// #![allow(incomplete_features)]
+    /// #![feature(generic_const_exprs)]
+    ///
+    /// use ctoption::builder::ArrayBuilder;
+    ///
+    /// let builder = ArrayBuilder::<i32, 65>::new();
+    /// ```
+    pub const fn new() -> Self {
+        let () = Capacity::<N>::VALID;
+
+        Self {
+            storage: [const { MaybeUninit::uninit() }; N],
+        }
+    }
+}
+
+impl<T, const N: usize> Default for ArrayBuilder<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize, const MASK: u64> ArrayBuilder<T, N, MASK> {
+    /// The number of set indices, i.e. the length of the built array.
+    pub const LEN: usize = MASK.count_ones() as usize;
+
+    /// Returns `true` if the value at index `I` is set.
+    ///
+    /// Querying an index that is out of bounds is a compile-time error:
+    ///
+    /// ```compile_fail
+    /// #![allow(incomplete_features)]
+    /// #![feature(generic_const_exprs)]
+    ///
+    /// use ctoption::builder::ArrayBuilder;
+    ///
+    /// let is_set = ArrayBuilder::<i32, 2>::new().is_set::<2>();
+    /// ```
+    pub const fn is_set<const I: usize>(&self) -> bool {
+        let () = InBounds::<N, I>::VALID;
+
+        MASK >> I & 1 == 1
+    }
+
+    /// Sets the value at index `I`.
+    ///
+    /// Setting an index that is out of bounds or already set is a compile-time error:
+    ///
+    /// ```compile_fail
+    /// #![allow(incomplete_features)]
+    /// #![feature(generic_const_exprs)]
+    ///
+    /// use ctoption::builder::ArrayBuilder;
+    ///
+    /// let builder = ArrayBuilder::<i32, 2>::new().set::<0>(1).set::<0>(2);
+    /// ```
+    pub const fn set<const I: usize>(self, val: T) -> ArrayBuilder<T, N, { MASK | 1 << I }> {
+        let () = SetIndex::<N, MASK, I>::VALID;
+
+        let this = ManuallyDrop::new(self);
+        let this = &this as *const ManuallyDrop<Self> as *const Self;
+        let mut storage = unsafe { core::ptr::read(&(*this).storage) };
+        storage[I] = MaybeUninit::new(val);
+        ArrayBuilder { storage }
+    }
+
+    /// Collects the set values into an array, preserving the order of the indices.
+    pub const fn build(self) -> [T; MASK.count_ones() as usize]
+    where
+        [(); MASK.count_ones() as usize]:,
+    {
+        let this = ManuallyDrop::new(self);
+        let this = &this as *const ManuallyDrop<Self> as *const Self;
+        let mut fields = [const { unset_field() }; N];
+        let mut i = 0;
+        while i < N {
+            fields[i].is_some = MASK >> i & 1 == 1;
+            fields[i].storage = unsafe { core::ptr::read(&(*this).storage[i]) };
+            i += 1;
+        }
+        to_array(fields)
+    }
+}
+
+/// Checks that the number of indices of [`ArrayBuilder`] fits in `MASK` at compile time.
+struct Capacity<const N: usize>;
+
+impl<const N: usize> Capacity<N> {
+    const VALID: () = assert!(
+        N <= u64::BITS as usize,
+        "`ArrayBuilder` supports at most 64 indices"
+    );
+}
+
+/// Checks that the index passed to [`ArrayBuilder`] is in bounds at compile time.
+struct InBounds<const N: usize, const I: usize>;
+
+impl<const N: usize, const I: usize> InBounds<N, I> {
+    const VALID: () = assert!(I < N, "the index is out of bounds");
+}
+
+/// Checks the index passed to [`ArrayBuilder::set`] at compile time.
+struct SetIndex<const N: usize, const MASK: u64, const I: usize>;
+
+impl<const N: usize, const MASK: u64, const I: usize> SetIndex<N, MASK, I> {
+    const VALID: () = {
+        let () = InBounds::<N, I>::VALID;
+        assert!(MASK >> I & 1 == 0, "the index is already set");
+    };
+}
+
+const fn unset_field<T>() -> CtOptionDyn<T> {
+    CtOptionDyn {
+        is_some: false,
+        storage: MaybeUninit::uninit(),
+    }
+}
+
+impl<T, const N: usize, const MASK: u64> Drop for ArrayBuilder<T, N, MASK> {
+    fn drop(&mut self) {
+        for (i, val) in self.storage.iter_mut().enumerate() {
+            if MASK >> i & 1 == 1 {
+                unsafe { val.assume_init_drop() }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{DropCounter, Droppy};
+
+    #[test]
+    fn builds_set_fields_in_order() {
+        let v = ArrayBuilder::<i32, 5>::new()
+            .set::<0>(1)
+            .set::<1>(2)
+            .set::<2>(3)
+            .set::<3>(3)
+            .set::<4>(5)
+            .build();
+        assert!(v == [1, 2, 3, 3, 5]);
+        assert!(core::mem::size_of_val(&v) == 5 * core::mem::size_of::<i32>());
+
+        let v = ArrayBuilder::<i32, 5>::new()
+            .set::<0>(1)
+            .set::<1>(2)
+            .set::<2>(3)
+            .set::<3>(3)
+            .build();
+        assert!(v == [1, 2, 3, 3]);
+        assert!(core::mem::size_of_val(&v) == 4 * core::mem::size_of::<i32>());
+
+        let v = ArrayBuilder::<i32, 5>::new()
+            .set::<3>(4)
+            .set::<1>(2)
+            .build();
+        assert!(v == [2, 4]);
+    }
+
+    #[test]
+    fn tracks_set_indices() {
+        let builder = ArrayBuilder::<u8, 3>::new().set::<1>(1);
+        assert!(!builder.is_set::<0>() && builder.is_set::<1>() && !builder.is_set::<2>());
+        const { assert!(ArrayBuilder::<u8, 3, 0b010>::LEN == 1) };
+    }
+
+    #[test]
+    fn drops_set_values_once() {
+        let drops = DropCounter::new();
+
+        let builder = ArrayBuilder::<Droppy, 4>::new()
+            .set::<0>(drops.droppy(()))
+            .set::<2>(drops.droppy(()));
+        assert!(drops.get() == 0);
+        drop(builder);
+        assert!(drops.get() == 2);
+
+        let arr = ArrayBuilder::<Droppy, 4>::new()
+            .set::<3>(drops.droppy(()))
+            .build();
+        assert!(drops.get() == 2);
+        drop(arr);
+        assert!(drops.get() == 3);
+    }
+}
@@ -0,0 +1,97 @@
+//! Runtime-flagged companion of [`CTOption`] for when the typestate has to be erased,
+//! e.g. after FFI, deserialization or dynamic dispatch.
+
+use core::mem::{ManuallyDrop, MaybeUninit};
+
+use crate::CTOption;
+
+/// The storage of a [`CTOption`] together with its flag recorded at runtime.
+///
+/// Values are obtained with [`CtOptionDyn::from_ct`] or [`CTOption::tag_erase`].
+pub struct CtOptionDyn<T> {
+    pub(crate) is_some: bool,
+    pub(crate) storage: MaybeUninit<T>,
+}
+
+impl<T> CtOptionDyn<T> {
+    /// Erases the typestate of `opt`, keeping the flag as a runtime value.
+    pub const fn from_ct<const IS_SOME_VAL: bool>(opt: CTOption<T, IS_SOME_VAL>) -> Self {
+        let (is_some, storage) = opt.into_parts();
+        CtOptionDyn { is_some, storage }
+    }
+
+    /// Returns the flag of the erased [`CTOption`].
+    pub const fn is_some(&self) -> bool {
+        self.is_some
+    }
+
+    /// Converts into an [`Option`], moving the value out if there is one.
+    pub const fn into_option(self) -> Option<T> {
+        let this = ManuallyDrop::new(self);
+        let this = &this as *const ManuallyDrop<Self> as *const Self;
+        unsafe {
+            if (*this).is_some {
+                Some((*this).storage.assume_init_read())
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// Creates an array of erased options by calling `f` with each index.
+///
+/// Since the flags vary per element, the options are erased into [`CtOptionDyn`]s.
+///
+/// ```
+/// use ctoption::erased::from_fn;
+///
+/// let evens = from_fn::<u32, 4>(|i| (i % 2 == 0).then_some(i as u32));
+/// assert!(evens.iter().filter(|opt| opt.is_some()).count() == 2);
+/// ```
+pub fn from_fn<T, const N: usize>(f: fn(usize) -> Option<T>) -> [CtOptionDyn<T>; N] {
+    core::array::from_fn(|i| match f(i) {
+        Some(val) => CtOptionDyn {
+            is_some: true,
+            storage: MaybeUninit::new(val),
+        },
+        None => CtOptionDyn {
+            is_some: false,
+            storage: MaybeUninit::uninit(),
+        },
+    })
+}
+
+impl<T> Drop for CtOptionDyn<T> {
+    fn drop(&mut self) {
+        if self.is_some {
+            unsafe { self.storage.assume_init_drop() }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn erased_options_recover_their_values() {
+        let some = CtOptionDyn::from_ct(CTSome::new(7));
+        assert!(some.is_some());
+        assert!(some.into_option() == Some(7));
+
+        let none = CTNone::<i32>::new().tag_erase();
+        assert!(!none.is_some());
+        assert!(none.into_option().is_none());
+    }
+
+    #[test]
+    fn from_fn_flags_follow_indices() {
+        let [a, b, c] = from_fn(|i| (i != 1).then_some(i * 10));
+        assert!(a.is_some() && !b.is_some() && c.is_some());
+        assert!(a.into_option() == Some(0));
+        assert!(b.into_option().is_none());
+        assert!(c.into_option() == Some(20));
+    }
+}
@@ -0,0 +1,98 @@
+//! Flag arithmetic for writing typestate combinators.
+//!
+//! With `generic_const_exprs`, short-circuiting operators like `&&` are rejected in
+//! type position. These functions can be used instead, e.g.
+//! `CTOption<(T, U), { and_flags(IS_SOME_VAL, Y) }>`.
+
+use crate::OptionalConstGeneric;
+
+/// Returns the flag of any [`OptionalConstGeneric`] implementor, i.e. `O::IS_SOME_VAL`.
+///
+/// ```
+/// use ctoption::prelude::*;
+///
+/// const _: () = assert!(flag::<CTSome<i32>>() && !flag::<CTNone<i32>>());
+/// ```
+pub const fn flag<O: OptionalConstGeneric>() -> bool {
+    O::IS_SOME_VAL
+}
+
+/// Returns `a & b`.
+pub const fn and_flags(a: bool, b: bool) -> bool {
+    a & b
+}
+
+/// Returns `a | b`.
+pub const fn or_flags(a: bool, b: bool) -> bool {
+    a | b
+}
+
+/// Returns `a ^ b`.
+pub const fn xor_flags(a: bool, b: bool) -> bool {
+    a ^ b
+}
+
+/// Returns `!a`.
+pub const fn not_flag(a: bool) -> bool {
+    !a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truth_tables() {
+        const {
+            assert!(!and_flags(false, false));
+            assert!(!and_flags(false, true));
+            assert!(!and_flags(true, false));
+            assert!(and_flags(true, true));
+
+            assert!(!or_flags(false, false));
+            assert!(or_flags(false, true));
+            assert!(or_flags(true, false));
+            assert!(or_flags(true, true));
+
+            assert!(!xor_flags(false, false));
+            assert!(xor_flags(false, true));
+            assert!(xor_flags(true, false));
+            assert!(!xor_flags(true, true));
+
+            assert!(not_flag(false));
+            assert!(!not_flag(true));
+        }
+    }
+
+    #[test]
+    fn flag_reads_implementors() {
+        use crate::prelude::*;
+
+        const {
+            assert!(flag::<CTSome<i32>>());
+            assert!(!flag::<CTNone<i32>>());
+            assert!(flag::<CTSome<CTNone<u8>>>());
+        }
+    }
+
+    #[cfg(feature = "generic_const_exprs")]
+    #[test]
+    fn usable_in_type_position() {
+        use crate::prelude::*;
+        use core::mem::MaybeUninit;
+
+        fn both<T, const X: bool, const Y: bool>(
+            a: CTOption<T, X>,
+            b: CTOption<T, Y>,
+        ) -> CTOption<(T, T), { and_flags(X, Y) }> {
+            let storage = match (a.into_value_and_flag(), b.into_value_and_flag()) {
+                (Ok(a), Ok(b)) => MaybeUninit::new((a, b)),
+                _ => MaybeUninit::uninit(),
+            };
+            unsafe { CTOption::from_maybe_uninit(storage) }
+        }
+
+        assert!(both(CTSome::new(1), CTSome::new(2)).into_inner() == (1, 2));
+        assert!(!both(CTSome::new(1), CTNone::new()).is_some());
+    }
+}
@@ -0,0 +1,102 @@
+//! Typestate-flavored analogues of [`core::iter::once`] and [`core::iter::empty`].
+
+use core::iter::FusedIterator;
+
+use crate::{CTNone, CTOption, CTSome};
+
+/// An iterator that yields a value exactly once. See [`ct_once`].
+pub struct CtOnce<T> {
+    inner: Option<CTSome<T>>,
+}
+
+/// An iterator that yields nothing. See [`ct_empty`].
+pub struct CtEmpty<T> {
+    inner: CTNone<T>,
+}
+
+/// Creates an iterator that yields `val` exactly once.
+pub const fn ct_once<T>(val: T) -> CtOnce<T> {
+    CtOnce {
+        inner: Some(CTSome::new(val)),
+    }
+}
+
+/// Creates an iterator that yields nothing.
+pub const fn ct_empty<T>() -> CtEmpty<T> {
+    CtEmpty {
+        inner: CTNone::new(),
+    }
+}
+
+impl<T> Iterator for CtOnce<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.inner.take().map(CTSome::into_inner)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.inner.is_some() as usize;
+        (len, Some(len))
+    }
+}
+
+impl<T> ExactSizeIterator for CtOnce<T> {}
+
+impl<T> FusedIterator for CtOnce<T> {}
+
+impl<T> Iterator for CtEmpty<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.inner.is_some() as usize;
+        (len, Some(len))
+    }
+}
+
+impl<T> ExactSizeIterator for CtEmpty<T> {}
+
+impl<T> FusedIterator for CtEmpty<T> {}
+
+/// Yields a reference to the contained value, if any, like `&Option<T>` does.
+impl<'a, T, const IS_SOME_VAL: bool> IntoIterator for &'a CTOption<T, IS_SOME_VAL> {
+    type Item = &'a T;
+    type IntoIter = core::option::IntoIter<&'a T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.get().into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn once_and_empty_counts() {
+        assert!(ct_once(3).count() == 1);
+        assert!(ct_empty::<i32>().count() == 0);
+
+        let mut once = ct_once(3);
+        assert!(once.len() == 1);
+        assert!(once.next() == Some(3));
+        assert!(once.next().is_none());
+    }
+
+    #[test]
+    fn options_iterate_by_reference() {
+        let some = CTSome::new(5);
+        let mut sum = 0;
+        for x in &some {
+            sum += x;
+        }
+        assert!(sum == 5);
+
+        let none = CTNone::<i32>::new();
+        assert!((&none).into_iter().next().is_none());
+    }
+}
@@ -1,5 +1,8 @@
 #![no_std]
-#![cfg_attr(feature = "const_trait_impl", feature(const_trait_impl))]
+#![cfg_attr(
+    feature = "const_trait_impl",
+    feature(const_trait_impl, const_destruct, const_drop_in_place)
+)]
 #![cfg_attr(feature = "core_intrinsics", feature(core_intrinsics))]
 #![cfg_attr(
     feature = "adt_const_params",
@@ -10,10 +13,48 @@
     feature = "const_precise_live_drops",
     feature(const_precise_live_drops)
 )]
+#![cfg_attr(feature = "unsize", feature(unsize))]
+#![cfg_attr(
+    feature = "generic_const_exprs",
+    allow(incomplete_features),
+    feature(generic_const_exprs)
+)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
-use core::mem::{ManuallyDrop, MaybeUninit};
+use core::{
+    mem::{ManuallyDrop, MaybeUninit},
+    pin::Pin,
+};
 
+#[macro_use]
+mod macros;
+pub mod array;
+#[cfg(feature = "generic_const_exprs")]
+pub mod builder;
+pub mod erased;
+pub mod flags;
+pub mod iter;
 pub mod prelude;
+pub mod typestate;
+
+#[cfg(test)]
+mod test_utils;
+
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+pub mod __private {
+    use crate::CTOption;
+
+    pub use alloc::{string::String, vec::Vec};
+
+    pub fn push_into<T, const IS_SOME_VAL: bool>(opt: CTOption<T, IS_SOME_VAL>, vec: &mut Vec<T>) {
+        if IS_SOME_VAL {
+            vec.push(unsafe { opt.assume_some() }.into_inner());
+        }
+    }
+}
 
 /// A compile-time alternative to [`Option`]. Unlike [`Option`],
 /// this type is guaranteed to have the same size and alignmemt as `T`.
@@ -136,6 +177,16 @@ pub mod prelude;
 /// different types. If you try to do this, you'll get
 /// [`error[E0532]: expected tuple struct or tuple variant, found type alias CTSome.`][E0532]
 ///
+/// For this particular function, the name is available as an associated constant:
+///
+/// ```
+/// use ctoption::prelude::*;
+///
+/// const fn get_ty_alias_name<T, const IS_SOME_VAL: bool>(_opt: &CTOption<T, IS_SOME_VAL>) -> &'static str {
+///     CTOption::<T, IS_SOME_VAL>::VARIANT_NAME
+/// }
+/// ```
+///
 /// Then what about matching against the value of `IS_SOME_VAL`? Let's say we want to write a cleanup function.
 ///
 /// ```compile_fail
@@ -182,6 +233,13 @@ pub type CTSome<T> = CTOption<T, true>;
 
 pub type CTNone<T> = CTOption<T, false>;
 
+/// A type that behaves like a [`CTOption`] with a known payload type and flag.
+///
+/// # Safety
+///
+/// The implementor must have the same size, alignment, and validity
+/// invariants as `CTOption<Self::Inner, Self::IS_SOME_VAL>`, because its
+/// values may be transmuted into that type.
 pub unsafe trait OptionalConstGeneric {
     type Inner;
     const IS_SOME_VAL: bool;
@@ -200,6 +258,17 @@ pub mod workarounds {
     impl<T: ConstParamTy> ConstParamTy for Option<T> {}
 
     impl<T> Option<T> {
+        /// Returns `true` if the option is [`Some`](Self::Some).
+        ///
+        /// The option is taken by value because borrowing isn't supported in generic
+        /// constants. The value, if any, is leaked, which is harmless for the types of
+        /// const parameters.
+        pub const fn is_some(self) -> bool {
+            let is_some = matches!(self, Self::Some(_));
+            core::mem::forget(self);
+            is_some
+        }
+
         #[cfg(feature = "const_precise_live_drops")]
         pub const fn into_core(self) -> core::option::Option<T> {
             match self {
@@ -215,6 +284,72 @@ unsafe impl<T, const IS_SOME_VAL: bool> OptionalConstGeneric for CTOption<T, IS_
     const IS_SOME_VAL: bool = IS_SOME_VAL;
 }
 
+/// Converts any [`OptionalConstGeneric`] implementor into the canonical [`CTOption`],
+/// so that custom option-like types can be used with the combinators of this crate.
+#[cfg(feature = "generic_const_exprs")]
+pub const fn project<O: OptionalConstGeneric>(opt: O) -> CTOption<O::Inner, { O::IS_SOME_VAL }>
+where
+    CTOption<O::Inner, { O::IS_SOME_VAL }>: Sized,
+{
+    union ProjectionUnion<P: OptionalConstGeneric>
+    where
+        CTOption<P::Inner, { P::IS_SOME_VAL }>: Sized,
+    {
+        md_opt: ManuallyDrop<P>,
+        md_ctopt: ManuallyDrop<CTOption<P::Inner, { P::IS_SOME_VAL }>>,
+    }
+
+    let md_opt = ManuallyDrop::new(opt);
+    let u = ProjectionUnion::<O> { md_opt };
+    let md_ctopt = unsafe { u.md_ctopt };
+    ManuallyDrop::into_inner(md_ctopt)
+}
+
+/// Returns a [`CTSome`] holding `val` if `COND` is `true` and a [`CTNone`] otherwise,
+/// in which case `val` is dropped.
+///
+/// This is the typestate analogue of [`bool::then_some`] for a compile-time condition.
+///
+/// ```
+/// use ctoption::prelude::*;
+///
+/// let some: CTSome<i32> = some_if(42);
+/// assert!(some.into_inner() == 42);
+///
+/// let none = some_if::<false, _>(42);
+/// assert!(!none.is_some());
+/// ```
+pub fn some_if<const COND: bool, T>(val: T) -> CTOption<T, COND> {
+    if COND {
+        CTOption(MaybeUninit::new(val))
+    } else {
+        CTOption(MaybeUninit::uninit())
+    }
+}
+
+/// Moves the [`Result`] into the option.
+///
+/// An `Err` becomes a [`CTSome`] holding it if `B` is `true`. Otherwise, there is no
+/// storage to keep it in and the error is dropped.
+///
+/// ```
+/// use ctoption::prelude::*;
+///
+/// let ok: CTSome<Result<i32, &str>> = flatten_result(Ok(CTSome::new(42)));
+/// assert!(ok.into_inner() == Ok(42));
+///
+/// let err: CTSome<Result<i32, &str>> = flatten_result(Err("invalid"));
+/// assert!(err.into_inner() == Err("invalid"));
+/// ```
+pub fn flatten_result<T, E, const B: bool>(
+    r: Result<CTOption<T, B>, E>,
+) -> CTOption<Result<T, E>, B> {
+    match r {
+        Ok(opt) => opt.map(Ok),
+        Err(err) => some_if(Err(err)),
+    }
+}
+
 #[cfg(feature = "core_intrinsics")]
 pub mod opt_const_generic {
     use super::{CTSome, OptionalConstGeneric};
@@ -237,6 +372,31 @@ impl<T> CTSome<T> {
         Self(MaybeUninit::new(val))
     }
 
+    /// Borrows the value, which is always present in a [`CTSome`].
+    pub const fn unwrap_ref(&self) -> &T {
+        unsafe { self.0.assume_init_ref() }
+    }
+
+    /// Mutably borrows the value, which is always present in a [`CTSome`].
+    pub const fn unwrap_mut(&mut self) -> &mut T {
+        unsafe { self.0.assume_init_mut() }
+    }
+
+    /// Wraps a reference into a [`CTSome`], i.e. a shorthand for `CTSome::<&T>::new(val)`.
+    pub const fn new_ref(val: &T) -> CTSome<&T> {
+        CTSome::new(val)
+    }
+
+    /// Wraps raw storage holding a value into a [`CTSome`].
+    ///
+    /// # Safety
+    ///
+    /// `value` must be initialized. The returned [`CTSome`] takes over the
+    /// responsibility of dropping it.
+    pub const unsafe fn from_some_parts(value: MaybeUninit<T>) -> Self {
+        Self(value)
+    }
+
     pub const fn into_inner(self) -> T {
         union CTSomeUnion<T> {
             md_ctsome: ManuallyDrop<CTSome<T>>,
@@ -251,6 +411,9 @@ impl<T> CTSome<T> {
         ManuallyDrop::into_inner(md_inner)
     }
 
+    /// # Safety
+    ///
+    /// `IS_SOME_VAL` must be `true`. Otherwise, the value will be leaked.
     pub const unsafe fn assume_const_generic_val<const IS_SOME_VAL: bool>(
         self,
     ) -> CTOption<T, IS_SOME_VAL> {
@@ -264,6 +427,83 @@ impl<T> CTSome<T> {
         let md_ctopt = unsafe { u.md_ctopt };
         ManuallyDrop::into_inner(md_ctopt)
     }
+
+    /// Re-tags the option as `CTOption<T, true>`.
+    ///
+    /// This is the safe counterpart of [`CTSome::assume_const_generic_val`] for the only
+    /// flag that matches the alias, which is useful where the flag is spelled out explicitly.
+    pub const fn into_some_tagged(self) -> CTOption<T, true> {
+        self
+    }
+
+    /// Swaps the values of two [`CTSome`]s without changing either's typestate.
+    pub const fn swap(&mut self, other: &mut CTSome<T>) {
+        unsafe { core::mem::swap(self.0.assume_init_mut(), other.0.assume_init_mut()) }
+    }
+
+    /// Derives a new value from the contained one, returning both.
+    ///
+    /// Since `f` only borrows the value, `T` doesn't have to be [`Clone`].
+    pub fn map_with<U>(self, f: fn(&T) -> U) -> (CTSome<T>, CTSome<U>) {
+        let derived = f(unsafe { self.0.assume_init_ref() });
+        (self, CTSome::new(derived))
+    }
+
+    /// Replaces the value with the result of applying `f` to it, keeping the typestate.
+    ///
+    /// # Panics
+    ///
+    /// While `f` runs, the value is moved out of `self`. If `f` panics, `self` would
+    /// be dropped again during unwinding, so the process is aborted instead
+    /// (via a panic while panicking).
+    pub fn replace_with(&mut self, f: fn(T) -> T) {
+        struct AbortOnUnwind;
+
+        impl Drop for AbortOnUnwind {
+            fn drop(&mut self) {
+                panic!("the closure passed to `CTSome::replace_with` panicked");
+            }
+        }
+
+        let guard = AbortOnUnwind;
+        let val = unsafe { self.0.assume_init_read() };
+        self.0.write(f(val));
+        core::mem::forget(guard);
+    }
+
+    /// Moves the value into a one-element array.
+    pub const fn into_array(self) -> [T; 1] {
+        [self.into_inner()]
+    }
+
+    /// Takes the value out if `pred` holds for it, leaving a [`CTNone`] behind.
+    ///
+    /// Otherwise, returns the option unchanged. This is the typestate analogue of
+    /// [`Option::take_if`].
+    pub fn take_if(self, pred: fn(&T) -> bool) -> Result<(T, CTNone<T>), CTSome<T>> {
+        if pred(unsafe { self.0.assume_init_ref() }) {
+            Ok((self.into_inner(), CTNone::new()))
+        } else {
+            Err(self)
+        }
+    }
+
+    /// Collects an iterator that yields at most one item.
+    ///
+    /// Returns `None` if the iterator is empty and `Some` with the only item otherwise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the iterator yields more than one item.
+    #[track_caller]
+    pub fn from_iter_at_most_one<I: IntoIterator<Item = T>>(iter: I) -> Option<Self> {
+        let mut iter = iter.into_iter();
+        let first = iter.next()?;
+        if iter.next().is_some() {
+            panic!("the iterator yielded more than one item");
+        }
+        Some(Self::new(first))
+    }
 }
 
 impl<T> CTNone<T> {
@@ -271,6 +511,28 @@ impl<T> CTNone<T> {
         Self(MaybeUninit::uninit())
     }
 
+    /// Re-tags the option as `CTOption<T, false>`.
+    ///
+    /// This is the safe counterpart of [`CTOption::assume_none`] when the source is
+    /// already known to be a [`CTNone`].
+    pub const fn into_none_tagged(self) -> CTOption<T, false> {
+        self
+    }
+
+    /// Wraps raw storage into a [`CTNone`].
+    ///
+    /// The contents of `storage` are treated as uninitialized and are never read
+    /// or dropped, so any value left in it is leaked.
+    pub const fn from_none_parts(storage: MaybeUninit<T>) -> Self {
+        Self(storage)
+    }
+
+    /// Returns an empty array, the [`CTNone`] counterpart of [`CTSome::into_array`].
+    pub const fn into_array(self) -> [T; 0] {
+        core::mem::forget(self);
+        []
+    }
+
     pub const fn insert(mut self, val: T) -> CTSome<T> {
         union CTOptionVariantUnion<T> {
             md_ctsome: ManuallyDrop<CTSome<T>>,
@@ -283,17 +545,203 @@ impl<T> CTNone<T> {
         let md_ctsome = unsafe { u.md_ctsome };
         ManuallyDrop::into_inner(md_ctsome)
     }
+
+    /// Initializes the value in place with `f` and turns the option into a [`CTSome`].
+    ///
+    /// Unlike [`CTNone::insert`], the value doesn't have to be constructed elsewhere
+    /// and moved in, which matters for large `T`.
+    ///
+    /// # Safety
+    ///
+    /// `f` must fully initialize the storage it is given.
+    pub unsafe fn emplace(mut self, f: impl FnOnce(&mut MaybeUninit<T>)) -> CTSome<T> {
+        f(&mut self.0);
+        unsafe { self.assume_some() }
+    }
+
+    /// Like [`CTNone::insert`] but the payload type may change.
+    pub const fn insert_mapped<U>(self, val: U) -> CTSome<U> {
+        core::mem::forget(self);
+        CTSome::new(val)
+    }
+
+    /// Inserts `val` only if it passes `validate`.
+    ///
+    /// On failure, `val` is dropped and the original [`CTNone`] is returned along with the error.
+    pub fn try_insert<E>(
+        self,
+        val: T,
+        validate: fn(&T) -> Result<(), E>,
+    ) -> Result<CTSome<T>, (CTNone<T>, E)> {
+        match validate(&val) {
+            Ok(()) => Ok(self.insert(val)),
+            Err(err) => Err((self, err)),
+        }
+    }
 }
 
 impl<T, const IS_SOME_VAL: bool> CTOption<T, IS_SOME_VAL> {
+    /// The number of values held by the option, i.e. `1` for [`CTSome`] and `0` for [`CTNone`].
+    pub const LEN: usize = IS_SOME_VAL as usize;
+
+    /// The name of the type alias matching the typestate, i.e. `"CTSome"` or `"CTNone"`.
+    pub const VARIANT_NAME: &'static str = if IS_SOME_VAL { "CTSome" } else { "CTNone" };
+
+    /// # Safety
+    ///
+    /// If `IS_SOME_VAL` is `true`, `val` must be initialized.
     pub const unsafe fn from_maybe_uninit(val: MaybeUninit<T>) -> Self {
         Self(val)
     }
 
+    /// Returns `true` if the option is [`CTSome`].
+    ///
+    /// The result is the const generic parameter `IS_SOME_VAL` itself, so the payload
+    /// is never read and, once monomorphized, the call folds to a literal.
+    #[inline(always)]
     pub const fn is_some(&self) -> bool {
         IS_SOME_VAL
     }
 
+    /// Returns a pointer to the storage of the value.
+    ///
+    /// Dereferencing the pointer is undefined behavior if `IS_SOME_VAL` is `false`.
+    pub const fn as_ptr(&self) -> *const T {
+        self.0.as_ptr()
+    }
+
+    /// Returns a mutable pointer to the storage of the value.
+    ///
+    /// Reading through the pointer is undefined behavior if `IS_SOME_VAL` is `false`.
+    pub const fn as_mut_ptr(&mut self) -> *mut T {
+        self.0.as_mut_ptr()
+    }
+
+    /// Borrows the raw storage of the value.
+    ///
+    /// The storage is initialized if and only if `IS_SOME_VAL` is `true`.
+    pub const fn as_maybe_uninit(&self) -> &MaybeUninit<T> {
+        &self.0
+    }
+
+    /// Mutably borrows the raw storage of the value.
+    ///
+    /// Writing to the storage doesn't change the typestate. A value written to a
+    /// [`CTNone`] is leaked unless the option is transitioned with
+    /// [`CTOption::assume_some`], and overwriting the value of a [`CTSome`] leaks
+    /// the old one.
+    ///
+    /// # Safety
+    ///
+    /// If `IS_SOME_VAL` is `true`, the storage must be left initialized.
+    pub const unsafe fn as_maybe_uninit_mut(&mut self) -> &mut MaybeUninit<T> {
+        &mut self.0
+    }
+
+    /// Reads the contained value without consuming the option.
+    ///
+    /// # Safety
+    ///
+    /// `IS_SOME_VAL` must be `true`. The caller must also ensure that the value
+    /// isn't dropped twice, e.g. by forgetting either the option or the returned
+    /// copy, unless `T` is [`Copy`].
+    pub const unsafe fn read_inner(&self) -> T {
+        unsafe { self.0.assume_init_read() }
+    }
+
+    /// Replaces `self` with `src`, returning the old option.
+    ///
+    /// Since the flag is a part of the type, both options must share the typestate.
+    /// Changing the typestate requires consuming the option instead, e.g. with
+    /// [`CTNone::insert`].
+    pub const fn replace(&mut self, src: Self) -> Self {
+        core::mem::replace(self, src)
+    }
+
+    /// Borrows the value without checking the typestate.
+    ///
+    /// # Safety
+    ///
+    /// `IS_SOME_VAL` must be `true`. Otherwise, the reference points to uninitialized
+    /// memory, which is undefined behavior. With `debug_assertions`, this is checked.
+    #[track_caller]
+    pub const unsafe fn get_unchecked(&self) -> &T {
+        debug_assert!(IS_SOME_VAL, "`get_unchecked` was called on a `CTNone`");
+        unsafe { self.0.assume_init_ref() }
+    }
+
+    /// Returns a reference to the contained value as a standard [`Option`],
+    /// for bridging to APIs expecting `Option<&T>`.
+    pub const fn as_option(&self) -> Option<&T> {
+        if IS_SOME_VAL {
+            Some(unsafe { self.0.assume_init_ref() })
+        } else {
+            None
+        }
+    }
+
+    /// Returns a reference to the contained value as a standard [`Option`].
+    ///
+    /// This is the same as [`CTOption::as_option`].
+    #[inline(always)]
+    pub const fn get(&self) -> Option<&T> {
+        self.as_option()
+    }
+
+    /// Returns the number of values held by the option, i.e. [`Self::LEN`].
+    #[inline(always)]
+    pub const fn count(&self) -> usize {
+        Self::LEN
+    }
+
+    /// Returns a stable discriminant of the typestate: `1` for [`CTSome`] and `0` for [`CTNone`].
+    ///
+    /// Unlike [`Hash`](core::hash::Hash), it is usable in const contexts. The payload is never read.
+    #[inline(always)]
+    pub const fn state_fingerprint(&self) -> u8 {
+        IS_SOME_VAL as u8
+    }
+
+    /// Returns `true` if both options share the same typestate. The values are not compared.
+    pub const fn same_state<const Y: bool>(&self, _other: &CTOption<T, Y>) -> bool {
+        IS_SOME_VAL == Y
+    }
+
+    /// Panics with `msg` unless the option is [`CTSome`].
+    ///
+    /// When evaluated in a const context, the panic becomes a compile-time error.
+    #[track_caller]
+    pub const fn expect_some(&self, msg: &str) {
+        if !IS_SOME_VAL {
+            panic!("{}", msg);
+        }
+    }
+
+    /// Panics with `msg` unless the option is [`CTNone`].
+    ///
+    /// When evaluated in a const context, the panic becomes a compile-time error.
+    #[track_caller]
+    pub const fn expect_none(&self, msg: &str) {
+        if IS_SOME_VAL {
+            panic!("{}", msg);
+        }
+    }
+
+    /// Converts from `Pin<&CTOption<T, IS_SOME_VAL>>` to `CTOption<Pin<&T>, IS_SOME_VAL>`.
+    pub const fn as_pin_ref(self: Pin<&Self>) -> CTOption<Pin<&T>, IS_SOME_VAL> {
+        if IS_SOME_VAL {
+            // SAFETY: the value is initialized and, being pinned together
+            // with `self`, is never moved out of it.
+            let pinned = unsafe { Pin::new_unchecked(self.get_ref().0.assume_init_ref()) };
+            CTOption(MaybeUninit::new(pinned))
+        } else {
+            CTOption(MaybeUninit::uninit())
+        }
+    }
+
+    /// # Safety
+    ///
+    /// `IS_SOME_VAL` must be `true`.
     pub const unsafe fn assume_some(self) -> CTSome<T> {
         union CTOptionVariantUnion<U, const NESTED_IS_SOME_VAL: bool> {
             md_ctsome: ManuallyDrop<CTSome<U>>,
@@ -306,6 +754,9 @@ impl<T, const IS_SOME_VAL: bool> CTOption<T, IS_SOME_VAL> {
         ManuallyDrop::into_inner(md_ctsome)
     }
 
+    /// # Safety
+    ///
+    /// `IS_SOME_VAL` must be `false`. Otherwise, the value will be leaked.
     pub const unsafe fn assume_none(self) -> CTNone<T> {
         union CTOptionVariantUnion<U, const NESTED_IS_SOME_VAL: bool> {
             md_ctnone: ManuallyDrop<CTNone<U>>,
@@ -317,56 +768,1870 @@ impl<T, const IS_SOME_VAL: bool> CTOption<T, IS_SOME_VAL> {
         let md_ctnone = unsafe { u.md_ctnone };
         ManuallyDrop::into_inner(md_ctnone)
     }
-}
 
-#[cfg(not(feature = "const_trait_impl"))]
-impl<T, const IS_SOME_VAL: bool> Drop for CTOption<T, IS_SOME_VAL> {
-    fn drop(&mut self) {
+    /// Views the option as a [`CTSome`] without moving it.
+    ///
+    /// # Safety
+    ///
+    /// `IS_SOME_VAL` must be `true`.
+    pub const unsafe fn assume_some_ref(&self) -> &CTSome<T> {
+        unsafe { &*(self as *const Self as *const CTSome<T>) }
+    }
+
+    /// Mutable version of [`CTOption::assume_some_ref`].
+    ///
+    /// # Safety
+    ///
+    /// `IS_SOME_VAL` must be `true`.
+    pub const unsafe fn assume_some_mut(&mut self) -> &mut CTSome<T> {
+        unsafe { &mut *(self as *mut Self as *mut CTSome<T>) }
+    }
+
+    /// Views the option as a [`CTNone`] without moving it.
+    ///
+    /// # Safety
+    ///
+    /// `IS_SOME_VAL` must be `false`.
+    pub const unsafe fn assume_none_ref(&self) -> &CTNone<T> {
+        unsafe { &*(self as *const Self as *const CTNone<T>) }
+    }
+
+    /// Mutable version of [`CTOption::assume_none_ref`].
+    ///
+    /// # Safety
+    ///
+    /// `IS_SOME_VAL` must be `false`.
+    pub const unsafe fn assume_none_mut(&mut self) -> &mut CTNone<T> {
+        unsafe { &mut *(self as *mut Self as *mut CTNone<T>) }
+    }
+
+    /// Moves the value out of the option without checking the typestate.
+    ///
+    /// # Safety
+    ///
+    /// `IS_SOME_VAL` must be `true`. Otherwise, an uninitialized value is read,
+    /// which is undefined behavior.
+    pub const unsafe fn into_inner_unchecked(self) -> T {
+        union CTOptionUnion<U, const NESTED_IS_SOME_VAL: bool> {
+            md_ctopt: ManuallyDrop<CTOption<U, NESTED_IS_SOME_VAL>>,
+            md_inner: ManuallyDrop<U>,
+        }
+
+        let md_ctopt = ManuallyDrop::new(self);
+        let u = CTOptionUnion { md_ctopt };
+        let md_inner = unsafe { u.md_inner };
+        ManuallyDrop::into_inner(md_inner)
+    }
+
+    /// Returns the contained value or panics with `msg` for [`CTNone`].
+    ///
+    /// When evaluated in a const context, the panic becomes a compile-time error
+    /// with `msg` in it.
+    #[track_caller]
+    pub const fn expect_const(self, msg: &'static str) -> T {
         if IS_SOME_VAL {
-            unsafe { self.0.assume_init_drop() }
+            unsafe { self.assume_some() }.into_inner()
+        } else {
+            core::mem::forget(self);
+            panic!("{}", msg)
         }
     }
-}
 
-#[cfg(feature = "const_trait_impl")]
-macro_rules! provide_items_guarded_by_const_trait_impl {
-    () => {
-        // The items are in a macro because they use a new syntax, which is not
-        // a valid Rust syntax at the moment of writing this.
-        pub const fn const_drop<T: ~const core::marker::Destruct>(val: T) {
-            core::mem::forget(val);
+    /// Returns the contained value, if any, as a standard [`Option`].
+    ///
+    /// This is the counterpart of [`CTSome::into_inner`] for options of any typestate.
+    pub const fn checked_into_inner(self) -> Option<T> {
+        if IS_SOME_VAL {
+            Some(unsafe { self.assume_some() }.into_inner())
+        } else {
+            core::mem::forget(self);
+            None
         }
+    }
 
-        impl<T, const IS_SOME_VAL: bool> const Drop for CTOption<T, IS_SOME_VAL> {
-            fn drop(&mut self) {
-                if IS_SOME_VAL {
-                    unsafe { self.0.assume_init_drop() }
-                }
-            }
+    /// Converts the option into a [`workarounds::Option`], which can be used as a
+    /// const generic parameter.
+    #[cfg(feature = "adt_const_params")]
+    pub const fn as_workaround(self) -> workarounds::Option<T> {
+        if IS_SOME_VAL {
+            workarounds::Option::Some(unsafe { self.assume_some() }.into_inner())
+        } else {
+            core::mem::forget(self);
+            workarounds::Option::None
         }
-    };
-}
+    }
 
-#[cfg(feature = "const_trait_impl")]
-provide_items_guarded_by_const_trait_impl!();
+    /// Returns `Ok` with the contained value for [`CTSome`] and `Err(())` for [`CTNone`].
+    #[allow(clippy::result_unit_err)]
+    pub const fn into_value_and_flag(self) -> Result<T, ()> {
+        if IS_SOME_VAL {
+            Ok(unsafe { self.assume_some() }.into_inner())
+        } else {
+            core::mem::forget(self);
+            Err(())
+        }
+    }
 
-#[cfg(test)]
-mod tests {
-    use crate::prelude::*;
+    /// Decomposes the option into its flag and its raw storage.
+    ///
+    /// The storage is initialized if and only if the flag is `true`.
+    pub const fn into_parts(self) -> (bool, MaybeUninit<T>) {
+        union CTOptionStorageUnion<U, const NESTED_IS_SOME_VAL: bool> {
+            md_ctopt: ManuallyDrop<CTOption<U, NESTED_IS_SOME_VAL>>,
+            md_storage: ManuallyDrop<MaybeUninit<U>>,
+        }
 
-    #[test]
-    const fn into_inner_works() {
-        let opt = CTSome::new(3);
-        let v = opt.into_inner();
-        if v != 3 {
-            panic!("v != 3");
+        let md_ctopt = ManuallyDrop::new(self);
+        let u = CTOptionStorageUnion { md_ctopt };
+        let md_storage = unsafe { u.md_storage };
+        (IS_SOME_VAL, ManuallyDrop::into_inner(md_storage))
+    }
+
+    /// Erases the typestate into a [`CtOptionDyn`](erased::CtOptionDyn) with a runtime flag.
+    pub const fn tag_erase(self) -> erased::CtOptionDyn<T> {
+        erased::CtOptionDyn::from_ct(self)
+    }
+
+    /// Resolves the typestate into the matching side of an [`Either`](either::Either):
+    /// [`CTSome`] on the left and [`CTNone`] on the right.
+    #[cfg(feature = "either")]
+    pub const fn into_either(self) -> either::Either<CTSome<T>, CTNone<T>> {
+        if IS_SOME_VAL {
+            either::Either::Left(unsafe { self.assume_some() })
+        } else {
+            either::Either::Right(unsafe { self.assume_none() })
         }
     }
 
-    #[test]
-    const fn test_insert() {
-        let none = CTNone::<i32>::new();
-        let some = none.insert(42);
-        assert!(some.into_inner() == 42);
+    /// Converts the option into a [`CTNone`] if it is one and returns it as a [`CTSome`] otherwise.
+    ///
+    /// This is the checked version of [`CTOption::assume_none`].
+    pub const fn try_into_none(self) -> Result<CTNone<T>, CTSome<T>> {
+        if IS_SOME_VAL {
+            Err(unsafe { self.assume_some() })
+        } else {
+            Ok(unsafe { self.assume_none() })
+        }
+    }
+
+    /// Maps the contained value, if any, with `f` while preserving the typestate.
+    pub fn map<U>(self, f: fn(T) -> U) -> CTOption<U, IS_SOME_VAL> {
+        if IS_SOME_VAL {
+            let val = unsafe { self.assume_some() }.into_inner();
+            CTOption(MaybeUninit::new(f(val)))
+        } else {
+            CTOption(MaybeUninit::uninit())
+        }
+    }
+
+    /// Returns `false` for [`CTNone`] and the result of `pred` applied to the value otherwise.
+    ///
+    /// The value is consumed. See [`CTOption::is_some_and_ref`] for a borrowing variant.
+    pub fn is_some_and(self, pred: fn(T) -> bool) -> bool {
+        IS_SOME_VAL && pred(unsafe { self.assume_some() }.into_inner())
+    }
+
+    /// Returns `false` for [`CTNone`] and the result of `pred` applied to a reference to the value otherwise.
+    pub fn is_some_and_ref(&self, pred: fn(&T) -> bool) -> bool {
+        IS_SOME_VAL && pred(unsafe { self.0.assume_init_ref() })
+    }
+
+    /// Computes a value from a reference to the contained value with `f` or with `default` for [`CTNone`].
+    ///
+    /// Unlike the consuming combinators, the option stays usable afterwards.
+    pub fn map_or_else_ref<U>(&self, default: fn() -> U, f: fn(&T) -> U) -> U {
+        if IS_SOME_VAL {
+            f(unsafe { self.0.assume_init_ref() })
+        } else {
+            default()
+        }
+    }
+
+    /// Returns `true` for [`CTNone`] and the result of `pred` applied to a reference to the value otherwise.
+    pub fn is_none_or(self, pred: fn(&T) -> bool) -> bool {
+        !IS_SOME_VAL || pred(unsafe { self.0.assume_init_ref() })
+    }
+
+    /// Returns the contained value or `fallback` for [`CTNone`].
+    ///
+    /// Unlike [`CTSome::into_inner`], this function is not `const` because
+    /// the unused `fallback` has to be dropped when the option is [`CTSome`].
+    pub fn into_inner_or(self, fallback: T) -> T {
+        if IS_SOME_VAL {
+            unsafe { self.assume_some() }.into_inner()
+        } else {
+            fallback
+        }
+    }
+
+    /// Returns `f` applied to the contained value or [`U::default()`](Default::default) for [`CTNone`].
+    pub fn map_or_default<U: Default>(self, f: fn(T) -> U) -> U {
+        if IS_SOME_VAL {
+            f(unsafe { self.assume_some() }.into_inner())
+        } else {
+            U::default()
+        }
+    }
+
+    /// Drops the contained value, if any, keeping only the typestate as a zero-sized marker.
+    pub fn finalize(self) -> CTOption<(), IS_SOME_VAL> {
+        drop(self);
+        CTOption(MaybeUninit::new(()))
     }
+
+    /// Calls `f` if the option is [`CTNone`] and returns the option unchanged.
+    ///
+    /// The (uninitialized) storage is never touched.
+    pub fn inspect_none(self, f: fn()) -> Self {
+        if !IS_SOME_VAL {
+            f();
+        }
+        self
+    }
+
+    /// Calls `f` on the contained value, if any, and returns `self` for chaining.
+    pub fn inspect_mut(&mut self, f: fn(&mut T)) -> &mut Self {
+        if IS_SOME_VAL {
+            f(unsafe { self.0.assume_init_mut() });
+        }
+        self
+    }
+
+    /// Returns the value of the only option holding one, if exactly one of `self` and
+    /// `other` does, and `None` otherwise.
+    ///
+    /// If both options hold values, both values are dropped.
+    pub fn xor_into<const Y: bool>(self, other: CTOption<T, Y>) -> Option<T> {
+        match (self.into_value_and_flag(), other.into_value_and_flag()) {
+            (Ok(val), Err(())) | (Err(()), Ok(val)) => Some(val),
+            _ => None,
+        }
+    }
+
+    /// Zips `self` with another option of the same typestate.
+    ///
+    /// Unlike `CTOption::zip`, this doesn't require `generic_const_exprs` because
+    /// the flag of the result is the shared flag `IS_SOME_VAL` rather than a
+    /// computed one. Options of different typestates don't typecheck.
+    pub const fn zip_same_flag<U>(
+        self,
+        other: CTOption<U, IS_SOME_VAL>,
+    ) -> CTOption<(T, U), IS_SOME_VAL> {
+        if IS_SOME_VAL {
+            let a = unsafe { self.assume_some() }.into_inner();
+            let b = unsafe { other.assume_some() }.into_inner();
+            CTOption(MaybeUninit::new((a, b)))
+        } else {
+            core::mem::forget((self, other));
+            CTOption(MaybeUninit::uninit())
+        }
+    }
+
+    /// Zips `self` with another option. The result is [`CTSome`] only if both options are.
+    ///
+    /// If only one of the options holds a value, that value is dropped.
+    #[cfg(feature = "generic_const_exprs")]
+    pub fn zip<U, const Y: bool>(
+        self,
+        other: CTOption<U, Y>,
+    ) -> CTOption<(T, U), { IS_SOME_VAL & Y }> {
+        if IS_SOME_VAL && Y {
+            let a = unsafe { self.assume_some() }.into_inner();
+            let b = unsafe { other.assume_some() }.into_inner();
+            CTOption(MaybeUninit::new((a, b)))
+        } else {
+            CTOption(MaybeUninit::uninit())
+        }
+    }
+
+    /// Returns `self` if it is [`CTSome`] and `other` otherwise.
+    ///
+    /// If both options hold values, the one of `other` is dropped.
+    #[cfg(feature = "generic_const_exprs")]
+    pub fn or<const Y: bool>(self, other: CTOption<T, Y>) -> CTOption<T, { IS_SOME_VAL | Y }> {
+        if IS_SOME_VAL {
+            CTOption(MaybeUninit::new(unsafe { self.assume_some() }.into_inner()))
+        } else if Y {
+            CTOption(MaybeUninit::new(
+                unsafe { other.assume_some() }.into_inner(),
+            ))
+        } else {
+            CTOption(MaybeUninit::uninit())
+        }
+    }
+
+    /// Merges `self` with another option. If both hold values, they are combined with `f`;
+    /// otherwise, the value of the one that holds it, if any, is kept.
+    #[cfg(feature = "generic_const_exprs")]
+    pub fn merge<const Y: bool>(
+        self,
+        other: CTOption<T, Y>,
+        f: fn(T, T) -> T,
+    ) -> CTOption<T, { IS_SOME_VAL | Y }> {
+        match (self.into_value_and_flag(), other.into_value_and_flag()) {
+            (Ok(a), Ok(b)) => CTOption(MaybeUninit::new(f(a, b))),
+            (Ok(val), Err(())) | (Err(()), Ok(val)) => CTOption(MaybeUninit::new(val)),
+            (Err(()), Err(())) => CTOption(MaybeUninit::uninit()),
+        }
+    }
+
+    /// Applies an optional function to the optional value. The result is [`CTSome`]
+    /// only if both the function and the value are present.
+    ///
+    /// If only the value is present, it is dropped.
+    #[cfg(feature = "generic_const_exprs")]
+    pub fn apply<U, const FB: bool>(
+        self,
+        f: CTOption<fn(T) -> U, FB>,
+    ) -> CTOption<U, { IS_SOME_VAL & FB }> {
+        if IS_SOME_VAL && FB {
+            let val = unsafe { self.assume_some() }.into_inner();
+            let f = unsafe { f.assume_some() }.into_inner();
+            CTOption(MaybeUninit::new(f(val)))
+        } else {
+            CTOption(MaybeUninit::uninit())
+        }
+    }
+
+    /// Zips `self` with two other options. The result is [`CTSome`] only if all options are.
+    #[cfg(feature = "generic_const_exprs")]
+    pub fn zip3<U, V, const Y: bool, const Z: bool>(
+        self,
+        b: CTOption<U, Y>,
+        c: CTOption<V, Z>,
+    ) -> CTOption<(T, U, V), { IS_SOME_VAL & Y & Z }>
+    where
+        CTOption<(T, U), { IS_SOME_VAL & Y }>: Sized,
+    {
+        self.zip(b).zip(c).map(|((a, b), c)| (a, b, c))
+    }
+
+    /// Zips `self` with three other options. The result is [`CTSome`] only if all options are.
+    #[cfg(feature = "generic_const_exprs")]
+    pub fn zip4<U, V, W, const Y: bool, const Z: bool, const Q: bool>(
+        self,
+        b: CTOption<U, Y>,
+        c: CTOption<V, Z>,
+        d: CTOption<W, Q>,
+    ) -> CTOption<(T, U, V, W), { IS_SOME_VAL & Y & Z & Q }>
+    where
+        CTOption<(T, U), { IS_SOME_VAL & Y }>: Sized,
+        CTOption<((T, U), V), { IS_SOME_VAL & Y & Z }>: Sized,
+    {
+        self.zip(b)
+            .zip(c)
+            .zip(d)
+            .map(|(((a, b), c), d)| (a, b, c, d))
+    }
+}
+
+impl<T> core::borrow::Borrow<T> for CTSome<T> {
+    fn borrow(&self) -> &T {
+        unsafe { self.0.assume_init_ref() }
+    }
+}
+
+impl<T: PartialEq> PartialEq<T> for CTSome<T> {
+    fn eq(&self, other: &T) -> bool {
+        unsafe { self.0.assume_init_ref() }.eq(other)
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+impl<T: bytemuck::Pod, const IS_SOME_VAL: bool> CTOption<T, IS_SOME_VAL> {
+    /// Returns the raw bytes of the value for [`CTSome`] and `None` for [`CTNone`].
+    ///
+    /// The uninitialized storage of a [`CTNone`] is never read.
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        if IS_SOME_VAL {
+            Some(bytemuck::bytes_of(unsafe { self.0.assume_init_ref() }))
+        } else {
+            None
+        }
+    }
+}
+
+impl<A, B, const IS_SOME_VAL: bool> CTOption<(A, B), IS_SOME_VAL> {
+    /// Maps both elements of the contained pair, if any, with separate functions.
+    pub fn map_both<C, D>(self, f: fn(A) -> C, g: fn(B) -> D) -> CTOption<(C, D), IS_SOME_VAL> {
+        if IS_SOME_VAL {
+            let (a, b) = unsafe { self.assume_some() }.into_inner();
+            CTOption(MaybeUninit::new((f(a), g(b))))
+        } else {
+            CTOption(MaybeUninit::uninit())
+        }
+    }
+
+    /// Maps the contained pair with `f` if `pred` holds for its first element, like
+    /// [`Iterator::map_while`] over a single item.
+    ///
+    /// Returns `None` without calling `pred` for [`CTNone`] and without calling `f`
+    /// if `pred` doesn't hold, in which case the pair is dropped. Since whether the
+    /// predicate holds is only known at runtime, the result is a standard [`Option`].
+    pub fn map_while<C, D>(self, pred: fn(&A) -> bool, f: fn(A, B) -> (C, D)) -> Option<(C, D)> {
+        if IS_SOME_VAL {
+            let (a, b) = unsafe { self.assume_some() }.into_inner();
+            if pred(&a) {
+                Some(f(a, b))
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    }
+}
+
+impl<T, const IS_SOME_VAL: bool> CTOption<(T, T), IS_SOME_VAL> {
+    /// Combines the elements of the contained pair, if any, into a single value with `f`.
+    pub fn reduce_pair(self, f: fn(T, T) -> T) -> CTOption<T, IS_SOME_VAL> {
+        if IS_SOME_VAL {
+            let (a, b) = unsafe { self.assume_some() }.into_inner();
+            CTOption(MaybeUninit::new(f(a, b)))
+        } else {
+            CTOption(MaybeUninit::uninit())
+        }
+    }
+}
+
+impl<T, E, const IS_SOME_VAL: bool> CTOption<Result<T, E>, IS_SOME_VAL> {
+    /// Maps the error of the contained [`Result`], if any, leaving an `Ok` untouched.
+    pub fn map_err<F>(self, f: fn(E) -> F) -> CTOption<Result<T, F>, IS_SOME_VAL> {
+        if IS_SOME_VAL {
+            let res = unsafe { self.assume_some() }.into_inner();
+            CTOption(MaybeUninit::new(res.map_err(f)))
+        } else {
+            CTOption(MaybeUninit::uninit())
+        }
+    }
+}
+
+impl<T, const IS_SOME_VAL: bool> CTOption<&mut T, IS_SOME_VAL> {
+    /// Reborrows the contained mutable reference for a shorter lifetime without consuming `self`.
+    pub const fn reborrow(&mut self) -> CTOption<&mut T, IS_SOME_VAL> {
+        if IS_SOME_VAL {
+            let val: &mut T = unsafe { self.0.assume_init_mut() };
+            CTOption(MaybeUninit::new(val))
+        } else {
+            CTOption(MaybeUninit::uninit())
+        }
+    }
+}
+
+#[cfg(feature = "generic_const_exprs")]
+impl<T, const INNER: bool, const OUTER: bool> CTOption<CTOption<T, INNER>, OUTER> {
+    /// Borrows the value of a nested option, if both levels hold one, without consuming `self`.
+    pub const fn flatten_ref(&self) -> CTOption<&T, { OUTER & INNER }> {
+        if OUTER && INNER {
+            let inner = unsafe { self.0.assume_init_ref() };
+            CTOption(MaybeUninit::new(unsafe { inner.0.assume_init_ref() }))
+        } else {
+            CTOption(MaybeUninit::uninit())
+        }
+    }
+}
+
+impl<T: Clone, const IS_SOME_VAL: bool> Clone for CTOption<T, IS_SOME_VAL> {
+    fn clone(&self) -> Self {
+        if IS_SOME_VAL {
+            Self(MaybeUninit::new(
+                unsafe { self.0.assume_init_ref() }.clone(),
+            ))
+        } else {
+            Self(MaybeUninit::uninit())
+        }
+    }
+
+    /// Reuses the storage of `self` by delegating to [`T::clone_from`](Clone::clone_from).
+    fn clone_from(&mut self, source: &Self) {
+        if IS_SOME_VAL {
+            unsafe {
+                self.0
+                    .assume_init_mut()
+                    .clone_from(source.0.assume_init_ref())
+            }
+        }
+    }
+}
+
+impl<T: Clone> CTSome<T> {
+    /// Clones the option into an array of `N` [`CTSome`]s.
+    pub fn repeat<const N: usize>(self) -> [CTSome<T>; N] {
+        core::array::from_fn(|_| self.clone())
+    }
+}
+
+impl<T: core::fmt::Display> core::fmt::Display for CTSome<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        unsafe { self.0.assume_init_ref() }.fmt(f)
+    }
+}
+
+impl<T> Default for CTNone<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Default> Default for CTSome<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const IS_SOME_VAL: bool> CTOption<alloc::boxed::Box<T>, IS_SOME_VAL> {
+    /// Moves the value out of the [`Box`](alloc::boxed::Box) and frees the allocation.
+    ///
+    /// For a [`CTNone`], no allocation has ever been made and nothing is freed.
+    pub fn unbox(self) -> CTOption<T, IS_SOME_VAL> {
+        if IS_SOME_VAL {
+            let boxed = unsafe { self.assume_some() }.into_inner();
+            CTOption(MaybeUninit::new(*boxed))
+        } else {
+            CTOption(MaybeUninit::uninit())
+        }
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "unsize"))]
+impl<T: ?Sized, const IS_SOME_VAL: bool> CTOption<alloc::boxed::Box<T>, IS_SOME_VAL> {
+    /// Coerces the contained [`Box`](alloc::boxed::Box), if any, to a box of an unsized type,
+    /// e.g. `Box<[u8; 4]>` to `Box<[u8]>`.
+    ///
+    /// [`CTOption`] can't implement [`CoerceUnsized`](core::ops::CoerceUnsized) because
+    /// its storage is a [`MaybeUninit`], which doesn't implement it either, so the
+    /// coercion has to be explicit.
+    pub fn unsize<U: ?Sized>(self) -> CTOption<alloc::boxed::Box<U>, IS_SOME_VAL>
+    where
+        T: core::marker::Unsize<U>,
+    {
+        if IS_SOME_VAL {
+            let boxed: alloc::boxed::Box<U> = unsafe { self.assume_some() }.into_inner();
+            CTOption(MaybeUninit::new(boxed))
+        } else {
+            CTOption(MaybeUninit::uninit())
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> CTSome<alloc::boxed::Box<T>> {
+    /// Consumes the option and leaks the [`Box`](alloc::boxed::Box), returning a mutable
+    /// reference with an arbitrary lifetime, e.g. `'static`.
+    ///
+    /// See [`Box::leak`](alloc::boxed::Box::leak).
+    pub fn leak<'a>(self) -> &'a mut T
+    where
+        T: 'a,
+    {
+        alloc::boxed::Box::leak(self.into_inner())
+    }
+}
+
+#[cfg(not(feature = "const_trait_impl"))]
+impl<T, const IS_SOME_VAL: bool> Drop for CTOption<T, IS_SOME_VAL> {
+    fn drop(&mut self) {
+        if IS_SOME_VAL {
+            unsafe { self.0.assume_init_drop() }
+        }
+    }
+}
+
+#[cfg(feature = "const_trait_impl")]
+macro_rules! provide_items_guarded_by_const_trait_impl {
+    () => {
+        // The items are in a macro because they use a new syntax, which is not
+        // a valid Rust syntax at the moment of writing this.
+        pub const fn const_drop<T: [const] core::marker::Destruct>(val: T) {
+            core::mem::forget(val);
+        }
+
+        impl<T: [const] core::marker::Destruct, const IS_SOME_VAL: bool> const Drop
+            for CTOption<T, IS_SOME_VAL>
+        {
+            fn drop(&mut self) {
+                if IS_SOME_VAL {
+                    unsafe { self.0.assume_init_drop() }
+                }
+            }
+        }
+
+        /// A callable that can be invoked during constant evaluation, unlike a function pointer.
+        ///
+        /// ```
+        /// #![feature(const_trait_impl)]
+        /// use ctoption::prelude::*;
+        ///
+        /// struct Double;
+        ///
+        /// impl const ConstFn<i32, i32> for Double {
+        ///     fn call(self, val: i32) -> i32 {
+        ///         val * 2
+        ///     }
+        /// }
+        ///
+        /// const _: () = {
+        ///     let some = CTSome::new(21).map_const(Double);
+        ///     assert!(some.into_inner() == 42);
+        /// };
+        /// ```
+        pub const trait ConstFn<T, U> {
+            fn call(self, val: T) -> U;
+        }
+
+        impl<T, const IS_SOME_VAL: bool> CTOption<T, IS_SOME_VAL> {
+            /// Maps the contained value, if any, with a [`ConstFn`] while preserving the typestate.
+            pub const fn map_const<U, F: [const] ConstFn<T, U>>(
+                self,
+                f: F,
+            ) -> CTOption<U, IS_SOME_VAL> {
+                if IS_SOME_VAL {
+                    let val = unsafe { self.assume_some() }.into_inner();
+                    CTOption(MaybeUninit::new(f.call(val)))
+                } else {
+                    core::mem::forget((self, f));
+                    CTOption(MaybeUninit::uninit())
+                }
+            }
+
+            /// Returns the contained value or computes it with a [`ConstFn`] for [`CTNone`].
+            ///
+            /// Unlike a function pointer, `f` can be called during constant evaluation.
+            pub const fn unwrap_or_else_const<F>(self, f: F) -> T
+            where
+                F: [const] ConstFn<(), T> + [const] core::marker::Destruct,
+            {
+                if IS_SOME_VAL {
+                    unsafe { self.assume_some() }.into_inner()
+                } else {
+                    core::mem::forget(self);
+                    f.call(())
+                }
+            }
+
+            /// Drops the option, which is callable during constant evaluation, unlike
+            /// letting a generic [`CTOption`] go out of scope in a `const fn`.
+            ///
+            /// ```
+            /// #![feature(const_trait_impl, const_destruct)]
+            /// use ctoption::prelude::*;
+            ///
+            /// const fn do_one_thing() {}
+            /// const fn do_another_thing() {}
+            ///
+            /// const fn extra_cleanup<T: [const] core::marker::Destruct, const IS_SOME_VAL: bool>(
+            ///     opt: CTOption<T, IS_SOME_VAL>,
+            /// ) {
+            ///     match IS_SOME_VAL {
+            ///         true => do_one_thing(),
+            ///         false => do_another_thing(),
+            ///     }
+            ///     opt.cleanup();
+            /// }
+            ///
+            /// const _: () = extra_cleanup(CTSome::new(42));
+            /// ```
+            pub const fn cleanup(self)
+            where
+                T: [const] core::marker::Destruct,
+            {
+                if IS_SOME_VAL {
+                    let _val = unsafe { self.assume_some() }.into_inner();
+                } else {
+                    core::mem::forget(self);
+                }
+            }
+        }
+
+        /// [`PartialEq`] usable during constant evaluation.
+        pub const trait ConstPartialEq {
+            fn const_eq(&self, other: &Self) -> bool;
+        }
+
+        impl_const_partial_eq_for_primitives!(
+            u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, bool, char
+        );
+
+        /// [`From`] usable during constant evaluation.
+        ///
+        /// It is implemented for the lossless numeric conversions.
+        pub const trait ConstFrom<T> {
+            fn const_from(val: T) -> Self;
+        }
+
+        impl_const_from_for_widenings!(
+            u8 => u16, u32, u64, u128, usize, i16, i32, i64, i128, isize;
+            u16 => u32, u64, u128, usize, i32, i64, i128;
+            u32 => u64, u128, i64, i128;
+            u64 => u128, i128;
+            i8 => i16, i32, i64, i128, isize;
+            i16 => i32, i64, i128, isize;
+            i32 => i64, i128;
+            i64 => i128;
+        );
+
+        impl<T, const IS_SOME_VAL: bool> CTOption<T, IS_SOME_VAL> {
+            /// Converts the contained value, if any, with [`ConstFrom`], e.g. to widen an integer.
+            pub const fn widen<U: [const] ConstFrom<T>>(self) -> CTOption<U, IS_SOME_VAL> {
+                if IS_SOME_VAL {
+                    let val = unsafe { self.assume_some() }.into_inner();
+                    CTOption(MaybeUninit::new(U::const_from(val)))
+                } else {
+                    core::mem::forget(self);
+                    CTOption(MaybeUninit::uninit())
+                }
+            }
+        }
+
+        impl<T: [const] ConstPartialEq, const IS_SOME_VAL: bool> const ConstPartialEq
+            for CTOption<T, IS_SOME_VAL>
+        {
+            fn const_eq(&self, other: &Self) -> bool {
+                if IS_SOME_VAL {
+                    unsafe { self.0.assume_init_ref().const_eq(other.0.assume_init_ref()) }
+                } else {
+                    true
+                }
+            }
+        }
+    };
+}
+
+#[cfg(feature = "const_trait_impl")]
+macro_rules! impl_const_partial_eq_for_primitives {
+    ($($ty:ty),*) => {
+        $(
+            impl const ConstPartialEq for $ty {
+                fn const_eq(&self, other: &Self) -> bool {
+                    *self == *other
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "const_trait_impl")]
+macro_rules! impl_const_from_for_widenings {
+    ($($from:ty => $($to:ty),+;)*) => {
+        $($(
+            impl const ConstFrom<$from> for $to {
+                fn const_from(val: $from) -> Self {
+                    val as $to
+                }
+            }
+        )+)*
+    };
+}
+
+#[cfg(feature = "const_trait_impl")]
+provide_items_guarded_by_const_trait_impl!();
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use crate::prelude::*;
+    use crate::test_utils::{DropCounter, Droppy};
+    use core::mem::MaybeUninit;
+
+    #[test]
+    const fn into_inner_works() {
+        let opt = CTSome::new(3);
+        let v = opt.into_inner();
+        if v != 3 {
+            panic!("v != 3");
+        }
+    }
+
+    #[test]
+    const fn test_insert() {
+        let none = CTNone::<i32>::new();
+        let some = none.insert(42);
+        assert!(some.into_inner() == 42);
+    }
+
+    #[test]
+    fn some_if_follows_condition() {
+        let drops = DropCounter::new();
+
+        let some = some_if::<true, _>(drops.droppy(()));
+        assert!(some.is_some());
+        assert!(drops.get() == 0);
+        drop(some);
+        assert!(drops.get() == 1);
+
+        let none = some_if::<false, _>(drops.droppy(()));
+        assert!(!none.is_some());
+        assert!(drops.get() == 2);
+    }
+
+    #[test]
+    fn flatten_result_moves_result_inside() {
+        let ok: CTSome<Result<i32, ()>> = flatten_result(Ok(CTSome::new(42)));
+        assert!(ok.into_inner() == Ok(42));
+
+        let none: CTNone<Result<i32, ()>> = flatten_result(Ok(CTNone::new()));
+        assert!(!none.is_some());
+
+        let err: CTSome<Result<i32, &str>> = flatten_result(Err("invalid"));
+        assert!(err.into_inner() == Err("invalid"));
+
+        let dropped: CTNone<Result<i32, &str>> = flatten_result(Err("invalid"));
+        assert!(!dropped.is_some());
+    }
+
+    #[test]
+    const fn unwrap_ref_and_mut_borrow_value() {
+        let mut some = CTSome::new(41);
+        *some.unwrap_mut() += 1;
+        assert!(*some.unwrap_ref() == 42);
+        assert!(some.into_inner() == 42);
+    }
+
+    #[test]
+    const fn new_ref_wraps_reference() {
+        let some = CTSome::new_ref(&42);
+        assert!(*some.into_inner() == 42);
+    }
+
+    #[test]
+    const fn from_parts_wraps_raw_storage() {
+        let some = unsafe { CTSome::from_some_parts(MaybeUninit::new(42)) };
+        assert!(some.into_inner() == 42);
+
+        let none = CTNone::<i32>::from_none_parts(MaybeUninit::uninit());
+        assert!(!none.is_some());
+        core::mem::forget(none);
+    }
+
+    #[test]
+    const fn tagged_conversions_preserve_state() {
+        let some: CTOption<i32, IS_SOME> = CTSome::new(42).into_some_tagged();
+        assert!(some.into_inner() == 42);
+
+        let none: CTOption<i32, IS_NONE> = CTNone::new().into_none_tagged();
+        assert!(!none.is_some());
+        core::mem::forget(none);
+    }
+
+    #[test]
+    const fn swap_exchanges_values() {
+        let mut a = CTSome::new(1);
+        let mut b = CTSome::new(2);
+        a.swap(&mut b);
+        assert!(a.into_inner() == 2);
+        assert!(b.into_inner() == 1);
+    }
+
+    #[test]
+    fn map_with_returns_source_and_derived() {
+        let (source, derived) = CTSome::new([1, 2, 3]).map_with(|arr| arr.len());
+        assert!(source.into_inner() == [1, 2, 3]);
+        assert!(derived.into_inner() == 3);
+    }
+
+    #[test]
+    fn replace_with_transforms_in_place() {
+        let mut some = CTSome::new(21);
+        some.replace_with(|x| x * 2);
+        assert!(some.into_inner() == 42);
+    }
+
+    #[test]
+    const fn into_array_matches_len() {
+        let [val] = CTSome::new(42).into_array();
+        assert!(val == 42);
+
+        let empty: [i32; 0] = CTNone::new().into_array();
+        assert!(empty.len() == CTNone::<i32>::LEN);
+    }
+
+    #[test]
+    fn take_if_takes_only_on_match() {
+        let (val, none) = CTSome::new(42).take_if(|x| *x > 0).ok().unwrap();
+        assert!(val == 42 && !none.is_some());
+
+        let some = CTSome::new(-1).take_if(|x| *x > 0).err().unwrap();
+        assert!(some.into_inner() == -1);
+    }
+
+    #[test]
+    fn variant_name_matches_alias() {
+        assert!(CTSome::<i32>::VARIANT_NAME == "CTSome");
+        assert!(CTNone::<i32>::VARIANT_NAME == "CTNone");
+    }
+
+    #[test]
+    fn from_iter_at_most_one_collects_zero_or_one() {
+        assert!(CTSome::<i32>::from_iter_at_most_one([]).is_none());
+
+        let one = CTSome::from_iter_at_most_one([42]);
+        assert!(one.map(CTSome::into_inner) == Some(42));
+    }
+
+    #[test]
+    #[should_panic(expected = "more than one item")]
+    fn from_iter_at_most_one_panics_on_many() {
+        let _ = CTSome::from_iter_at_most_one([1, 2]);
+    }
+
+    #[test]
+    fn len_matches_flag() {
+        const {
+            assert!(CTSome::<i32>::LEN == 1);
+            assert!(CTNone::<i32>::LEN == 0);
+        }
+    }
+
+    #[test]
+    fn is_some_folds_to_constant() {
+        const SOME_FLAG: bool = {
+            let some = CTSome::new(1);
+            let flag = some.is_some();
+            some.into_inner();
+            flag
+        };
+        const NONE_FLAG: bool = {
+            let none = CTNone::<i32>::new();
+            let flag = none.is_some();
+            core::mem::forget(none);
+            flag
+        };
+        const {
+            assert!(SOME_FLAG);
+            assert!(!NONE_FLAG);
+        }
+    }
+
+    #[test]
+    const fn pointers_read_back_value() {
+        let mut some = CTSome::new(41);
+        unsafe { *some.as_mut_ptr() += 1 };
+        assert!(unsafe { *some.as_ptr() } == 42);
+        assert!(some.into_inner() == 42);
+    }
+
+    #[test]
+    const fn count_is_zero_or_one() {
+        let some = CTSome::new(42);
+        assert!(some.count() == 1);
+        assert!(some.into_inner() == 42);
+
+        let none = CTNone::<i32>::new();
+        assert!(none.count() == 0);
+        core::mem::forget(none);
+    }
+
+    #[test]
+    const fn maybe_uninit_exposes_storage() {
+        let mut some = CTSome::new(1);
+        unsafe { some.as_maybe_uninit_mut() }.write(42);
+        assert!(unsafe { *some.as_maybe_uninit().assume_init_ref() } == 42);
+        assert!(some.into_inner() == 42);
+
+        let mut none = CTNone::<i32>::new();
+        unsafe { none.as_maybe_uninit_mut() }.write(7);
+        assert!(unsafe { none.assume_some() }.into_inner() == 7);
+    }
+
+    #[test]
+    const fn read_inner_copies_value() {
+        let some = CTSome::new(42u8);
+        assert!(unsafe { some.read_inner() } == 42);
+        assert!(some.into_inner() == 42);
+    }
+
+    #[test]
+    const fn state_fingerprint_is_discriminant() {
+        let some = CTSome::new(42);
+        assert!(some.state_fingerprint() == 1);
+        assert!(some.into_inner() == 42);
+
+        let none = CTNone::<i32>::new();
+        assert!(none.state_fingerprint() == 0);
+        core::mem::forget(none);
+    }
+
+    #[test]
+    const fn replace_keeps_typestate() {
+        let mut some = CTSome::new(1);
+        let old = some.replace(CTSome::new(2));
+        assert!(old.into_inner() == 1);
+        assert!(some.into_inner() == 2);
+    }
+
+    #[test]
+    const fn get_unchecked_borrows_value() {
+        let some = CTSome::new(42);
+        assert!(*unsafe { some.get_unchecked() } == 42);
+        assert!(some.into_inner() == 42);
+    }
+
+    #[test]
+    fn get_borrows_by_flag() {
+        const {
+            let some = CTSome::new(42);
+            assert!(matches!(some.get(), Some(&42)));
+            assert!(some.into_inner() == 42);
+
+            let none = CTNone::<i32>::new();
+            assert!(none.get().is_none());
+            core::mem::forget(none);
+        }
+    }
+
+    #[test]
+    fn same_state_compares_flags_only() {
+        const {
+            let some0 = CTSome::new(1);
+            let some1 = CTSome::new(2);
+            let none0 = CTNone::<i32>::new();
+            let none1 = CTNone::<i32>::new();
+            assert!(some0.same_state(&some1));
+            assert!(none0.same_state(&none1));
+            assert!(!some0.same_state(&none0));
+            assert!(!none1.same_state(&some1));
+            assert!(some0.into_inner() + some1.into_inner() == 3);
+            core::mem::forget(none0);
+            core::mem::forget(none1);
+        }
+    }
+
+    #[test]
+    const fn expect_matching_typestate() {
+        let some = CTSome::new(42);
+        some.expect_some("must be some");
+        assert!(some.into_inner() == 42);
+
+        let none = CTNone::<i32>::new();
+        none.expect_none("must be none");
+        core::mem::forget(none);
+    }
+
+    #[test]
+    #[should_panic(expected = "field0 must be set")]
+    fn expect_some_panics_for_none() {
+        CTNone::<i32>::new().expect_some("field0 must be set");
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn as_bytes_exposes_payload() {
+        let some = CTSome::new(0x0102_0304u32);
+        assert!(some.as_bytes() == Some(&0x0102_0304u32.to_ne_bytes()[..]));
+        assert!(CTNone::<u32>::new().as_bytes().is_none());
+    }
+
+    #[test]
+    fn map_both_maps_each_element() {
+        let some = CTSome::new((1, 'a')).map_both(|x| x + 1, |c| c.is_alphabetic());
+        assert!(some.into_inner() == (2, true));
+
+        let none = CTNone::<(i32, char)>::new().map_both(|x| x + 1, |c| c.is_alphabetic());
+        assert!(!none.is_some());
+    }
+
+    #[test]
+    fn map_while_short_circuits_on_predicate() {
+        let mapped = CTSome::new((2, 'a')).map_while(|a| *a > 0, |a, b| (b, a * 21));
+        assert!(mapped == Some(('a', 42)));
+
+        let stopped: Option<(char, i32)> =
+            CTSome::new((0, 'a')).map_while(|a| *a > 0, |_, _| panic!("`f` must not be called"));
+        assert!(stopped.is_none());
+
+        let none = CTNone::<(i32, char)>::new()
+            .map_while(|_| panic!("`pred` must not be called"), |a, b| (b, a));
+        assert!(none.is_none());
+    }
+
+    #[test]
+    fn reduce_pair_combines_elements() {
+        let some = CTSome::new((40, 2)).reduce_pair(|a, b| a + b);
+        assert!(some.into_inner() == 42);
+
+        let none = CTNone::<(i32, i32)>::new().reduce_pair(|a, b| a + b);
+        assert!(!none.is_some());
+    }
+
+    #[test]
+    fn map_err_maps_contained_error() {
+        let err = CTSome::new(Err::<i32, u8>(1)).map_err(|e| e as u32 + 1);
+        assert!(err.into_inner() == Err(2));
+
+        let ok = CTSome::new(Ok::<i32, u8>(42)).map_err(|e| e as u32 + 1);
+        assert!(ok.into_inner() == Ok(42));
+
+        let none = CTNone::<Result<i32, u8>>::new().map_err(|e| e as u32 + 1);
+        assert!(!none.is_some());
+    }
+
+    #[test]
+    fn reborrow_keeps_original_usable() {
+        fn increment(opt: CTSome<&mut i32>) {
+            *opt.into_inner() += 1;
+        }
+
+        let mut val = 41;
+        let mut some = CTSome::new(&mut val);
+        increment(some.reborrow());
+        increment(some.reborrow());
+        *some.into_inner() += 1;
+        assert!(val == 44);
+    }
+
+    #[test]
+    fn as_pin_ref_points_at_value() {
+        use core::pin::pin;
+
+        let some = pin!(CTSome::new(42));
+        let addr = &*some as *const CTSome<i32> as *const i32;
+        let pinned = some.as_ref().as_pin_ref().into_inner();
+        assert!(core::ptr::eq(pinned.get_ref(), addr));
+        assert!(*pinned == 42);
+
+        let none = pin!(CTNone::<i32>::new());
+        assert!(!none.as_ref().as_pin_ref().is_some());
+    }
+
+    #[test]
+    const fn into_inner_unchecked_moves_value() {
+        const fn take<const IS_SOME_VAL: bool>(opt: CTOption<i32, IS_SOME_VAL>) -> i32 {
+            assert!(IS_SOME_VAL);
+            unsafe { opt.into_inner_unchecked() }
+        }
+
+        assert!(take(CTSome::new(42)) == 42);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "`get_unchecked` was called on a `CTNone`")]
+    fn get_unchecked_checks_flag_in_debug() {
+        let none = CTNone::<i32>::new();
+        let _ = unsafe { none.get_unchecked() };
+    }
+
+    #[test]
+    fn expect_const_returns_value() {
+        const {
+            assert!(CTSome::new(42).expect_const("the value must be set") == 42);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "the value must be set")]
+    fn expect_const_panics_for_none() {
+        CTNone::<i32>::new().expect_const("the value must be set");
+    }
+
+    #[test]
+    fn checked_into_inner_consumes_once() {
+        let drops = DropCounter::new();
+
+        let some = CTSome::new(drops.droppy(())).checked_into_inner();
+        assert!(some.is_some());
+        assert!(drops.get() == 0);
+        drop(some);
+        assert!(drops.get() == 1);
+
+        assert!(CTNone::<Droppy>::new().checked_into_inner().is_none());
+        assert!(drops.get() == 1);
+    }
+
+    #[cfg(feature = "adt_const_params")]
+    #[test]
+    fn as_workaround_matches_flag() {
+        assert!(CTSome::new(42).as_workaround() == crate::workarounds::Option::Some(42));
+        assert!(CTNone::<i32>::new().as_workaround() == crate::workarounds::Option::None);
+    }
+
+    #[cfg(all(feature = "adt_const_params", feature = "generic_const_exprs"))]
+    #[test]
+    fn from_workaround_derives_flag() {
+        use crate::workarounds::Option;
+
+        fn ct<const P: Option<u32>>() -> CTOption<u32, { P.is_some() }> {
+            from_workaround!(P)
+        }
+
+        assert!(ct::<{ Option::Some(42) }>().into_inner() == 42);
+        assert!(!ct::<{ Option::None }>().is_some());
+    }
+
+    #[test]
+    const fn into_value_and_flag_and_parts() {
+        assert!(matches!(CTSome::new(42).into_value_and_flag(), Ok(42)));
+        assert!(matches!(
+            CTNone::<i32>::new().into_value_and_flag(),
+            Err(())
+        ));
+
+        let (flag, storage) = CTSome::new(42).into_parts();
+        assert!(flag);
+        assert!(unsafe { storage.assume_init() } == 42);
+
+        let (flag, _storage) = CTNone::<i32>::new().into_parts();
+        assert!(!flag);
+    }
+
+    #[cfg(feature = "either")]
+    #[test]
+    fn into_either_resolves_sides() {
+        use either::Either;
+
+        match CTSome::new(42).into_either() {
+            Either::Left(some) => assert!(some.into_inner() == 42),
+            Either::Right(_) => panic!("expected CTSome"),
+        }
+
+        assert!(CTNone::<i32>::new().into_either().is_right());
+    }
+
+    #[test]
+    fn try_into_none_checks_flag() {
+        let drops = DropCounter::new();
+
+        let none = CTNone::<Droppy>::new().try_into_none();
+        assert!(none.is_ok());
+        drop(none);
+        assert!(drops.get() == 0);
+
+        let some = CTSome::new(drops.droppy(())).try_into_none();
+        assert!(drops.get() == 0);
+        assert!(some.is_err());
+        drop(some);
+        assert!(drops.get() == 1);
+    }
+
+    #[test]
+    fn map_preserves_typestate() {
+        let some = CTSome::new(2).map(|x| x * 21);
+        assert!(some.into_inner() == 42);
+
+        let none = CTNone::<i32>::new().map(|x| x * 21);
+        assert!(!none.is_some());
+    }
+
+    #[test]
+    fn is_some_and_checks_predicate() {
+        assert!(CTSome::new(42).is_some_and(|x| x > 0));
+        assert!(!CTSome::new(-1).is_some_and(|x| x > 0));
+        assert!(!CTNone::<i32>::new().is_some_and(|_| true));
+
+        let some = CTSome::new(42);
+        assert!(some.is_some_and_ref(|x| *x > 0));
+        assert!(!some.is_some_and_ref(|x| *x < 0));
+        assert!(!CTNone::<i32>::new().is_some_and_ref(|_| true));
+    }
+
+    #[test]
+    fn is_none_or_checks_predicate() {
+        assert!(CTNone::<i32>::new().is_none_or(|_| false));
+        assert!(CTSome::new(42).is_none_or(|x| *x > 0));
+        assert!(!CTSome::new(-1).is_none_or(|x| *x > 0));
+    }
+
+    #[test]
+    fn into_inner_or_falls_back_for_none() {
+        assert!(CTSome::new(42).into_inner_or(0) == 42);
+        assert!(CTNone::new().into_inner_or(0) == 0);
+    }
+
+    #[test]
+    fn map_or_default_folds_both_branches() {
+        assert!(CTSome::new(21).map_or_default(|x| x * 2) == 42);
+        assert!(CTNone::<i32>::new().map_or_default(|_| -> i32 { unreachable!() }) == 0);
+    }
+
+    #[test]
+    fn inspect_mut_runs_only_for_some() {
+        let mut some = CTSome::new(20);
+        some.inspect_mut(|x| *x += 1).inspect_mut(|x| *x *= 2);
+        assert!(some.into_inner() == 42);
+
+        let mut none = CTNone::<i32>::new();
+        none.inspect_mut(|_| panic!("`f` must not be called for `CTNone`"));
+    }
+
+    #[test]
+    fn xor_into_extracts_single_value() {
+        assert!(CTSome::new(1).xor_into(CTNone::new()) == Some(1));
+        assert!(CTNone::new().xor_into(CTSome::new(2)) == Some(2));
+        assert!(CTSome::new(1).xor_into(CTSome::new(2)).is_none());
+        assert!(CTNone::<i32>::new().xor_into(CTNone::new()).is_none());
+    }
+
+    #[test]
+    const fn zip_same_flag_pairs_values() {
+        let some = CTSome::new(1).zip_same_flag(CTSome::new('a'));
+        assert!(matches!(some.into_inner(), (1, 'a')));
+
+        let none = CTNone::<i32>::new().zip_same_flag(CTNone::<char>::new());
+        assert!(!none.is_some());
+        core::mem::forget(none);
+    }
+
+    #[test]
+    fn finalize_keeps_only_the_flag() {
+        let some = CTSome::new([0u64; 4]).finalize();
+        assert!(core::mem::size_of_val(&some) == 0);
+        assert!(some.is_some());
+
+        let none = CTNone::<[u64; 4]>::new().finalize();
+        assert!(core::mem::size_of_val(&none) == 0);
+        assert!(!none.is_some());
+    }
+
+    #[test]
+    fn inspect_none_runs_only_for_none() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        fn count() {
+            CALLS.fetch_add(1, Ordering::Relaxed);
+        }
+
+        let some = CTSome::new(42).inspect_none(count);
+        assert!(CALLS.load(Ordering::Relaxed) == 0);
+        assert!(some.into_inner() == 42);
+
+        let _none = CTNone::<i32>::new().inspect_none(count);
+        assert!(CALLS.load(Ordering::Relaxed) == 1);
+    }
+
+    #[test]
+    const fn ct_match_picks_branch_by_flag() {
+        let some = ct_match!(CTSome::new(41),
+            CTSome(x) => x + 1,
+            CTNone => 0,
+        );
+        assert!(some == 42);
+
+        let none = ct_match!(CTNone::<i32>::new(),
+            CTSome(x) => x + 1,
+            CTNone => 0,
+        );
+        assert!(none == 0);
+    }
+
+    #[test]
+    fn count_set_sums_flags() {
+        const {
+            assert!(count_set!() == 0);
+            assert!(count_set!(CTNone<i32>, CTNone<u8>) == 0);
+            assert!(count_set!(CTSome<i32>, CTNone<i32>, CTSome<u8>, CTSome<()>) == 3);
+        }
+    }
+
+    #[test]
+    fn ct_fold_skips_none_fields() {
+        let sum = ct_fold!(
+            0,
+            |acc: i32, val: i32| acc + val,
+            CTSome::new(1),
+            CTNone::new(),
+            CTSome::new(3),
+            CTNone::new(),
+            CTSome::new(5),
+        );
+        assert!(sum == 9);
+
+        let product = ct_fold!(1, |acc: i32, val: i32| acc * val);
+        assert!(product == 1);
+    }
+
+    #[test]
+    fn ct_sum_and_product_skip_none_fields() {
+        const {
+            assert!(ct_sum!(CTSome::new(1), CTNone::<i32>::new(), CTSome::new(3)) == 4);
+            assert!(ct_product!(CTSome::new(2u8), CTNone::<u8>::new(), CTSome::new(3)) == 6);
+        }
+
+        let empty: u32 = ct_sum!();
+        assert!(empty == 0);
+    }
+
+    #[test]
+    fn matches_some_reflects_flag() {
+        assert!(matches_some!(CTSome::new(42)));
+        assert!(!matches_some!(CTNone::<i32>::new()));
+    }
+
+    #[test]
+    fn ct_flags_collects_flags() {
+        let fields = (
+            CTSome::new(1),
+            CTNone::<u8>::new(),
+            CTSome::new("c"),
+            CTNone::<()>::new(),
+        );
+        let flags = ct_flags!(fields.0, fields.1, fields.2, fields.3);
+        assert!(flags == [true, false, true, false]);
+    }
+
+    #[test]
+    fn ct_pack_flags_sets_bits_of_some() {
+        const fn pack<const A: bool, const B: bool, const C: bool>(
+            fields: &(CTOption<i32, A>, CTOption<u8, B>, CTOption<char, C>),
+        ) -> u32 {
+            ct_pack_flags!(fields.0, fields.1, fields.2)
+        }
+
+        let fields = (CTSome::new(1), CTNone::new(), CTSome::new('c'));
+        assert!(pack(&fields) == 0b101);
+        const FIELDS: &(CTNone<i32>, CTSome<u8>, CTSome<char>) =
+            &(CTNone::new(), CTSome::new(2), CTSome::new('c'));
+        const { assert!(pack(FIELDS) == 0b110) };
+        assert!(ct_pack_flags!() == 0);
+    }
+
+    #[test]
+    fn clone_from_reuses_storage() {
+        struct Buf {
+            data: [u8; 4],
+            reused: bool,
+        }
+
+        impl Clone for Buf {
+            fn clone(&self) -> Self {
+                Self {
+                    data: self.data,
+                    reused: false,
+                }
+            }
+
+            fn clone_from(&mut self, source: &Self) {
+                self.data = source.data;
+                self.reused = true;
+            }
+        }
+
+        let source = CTSome::new(Buf {
+            data: [1, 2, 3, 4],
+            reused: false,
+        });
+        let clone = source.clone();
+        assert!(clone
+            .get()
+            .is_some_and(|buf| buf.data == [1, 2, 3, 4] && !buf.reused));
+
+        let mut dst = CTSome::new(Buf {
+            data: [0; 4],
+            reused: false,
+        });
+        dst.clone_from(&source);
+        let dst = dst.into_inner();
+        assert!(dst.data == [1, 2, 3, 4]);
+        assert!(dst.reused);
+
+        let mut none = CTNone::<Buf>::new();
+        none.clone_from(&CTNone::new());
+        assert!(!none.clone().is_some());
+    }
+
+    #[test]
+    fn defaults_match_typestate() {
+        #[derive(Default)]
+        struct Fields {
+            set: CTSome<i32>,
+            unset: CTNone<i32>,
+        }
+
+        let fields = Fields::default();
+        assert!(fields.set.into_inner() == 0);
+        assert!(!fields.unset.is_some());
+    }
+
+    #[test]
+    fn ctsome_borrows_as_inner() {
+        use core::borrow::Borrow;
+
+        fn first_byte<B: Borrow<[u8; 3]>>(bytes: B) -> u8 {
+            bytes.borrow()[0]
+        }
+
+        assert!(first_byte(CTSome::new(*b"abc")) == b'a');
+        let some = CTSome::new(*b"xyz");
+        let borrowed: &[u8; 3] = some.borrow();
+        assert!(borrowed == b"xyz");
+    }
+
+    #[test]
+    fn ctsome_compares_to_bare_value() {
+        assert!(CTSome::new(42) == 42);
+        assert!(CTSome::new(42) != 0);
+    }
+
+    #[test]
+    fn repeat_clones_into_array() {
+        let arr: [CTSome<i32>; 3] = CTSome::new(7).repeat();
+        assert!(arr.iter().all(|some| *some == 7));
+    }
+
+    #[test]
+    fn display_shows_inner_value() {
+        assert!(std::format!("{}", CTSome::new(42)) == "42");
+    }
+
+    #[cfg(feature = "generic_const_exprs")]
+    #[test]
+    fn ct_or_all_picks_first_set() {
+        let first = ct_or_all!(CTSome::new(1), CTSome::new(2), CTNone::new());
+        assert!(first.into_inner() == 1);
+
+        let middle = ct_or_all!(CTNone::new(), CTSome::new(2), CTSome::new(3));
+        assert!(middle.into_inner() == 2);
+
+        let none = ct_or_all!(CTNone::<i32>::new(), CTNone::new(), CTNone::new());
+        assert!(!none.is_some());
+    }
+
+    #[cfg(feature = "generic_const_exprs")]
+    #[test]
+    fn apply_requires_function_and_value() {
+        let drops = DropCounter::new();
+
+        let f: fn(Droppy<i32>) -> i32 = |d| d.val + 1;
+
+        let some = CTSome::new(drops.droppy(41)).apply(CTSome::new(f));
+        assert!(some.into_inner() == 42);
+        assert!(drops.get() == 1);
+
+        let none = CTSome::new(drops.droppy(41)).apply(CTNone::<fn(Droppy<i32>) -> i32>::new());
+        assert!(!none.is_some());
+        assert!(drops.get() == 2);
+
+        let none = CTNone::<Droppy<i32>>::new().apply(CTSome::new(f));
+        assert!(!none.is_some());
+        assert!(drops.get() == 2);
+    }
+
+    #[cfg(feature = "generic_const_exprs")]
+    #[test]
+    fn merge_combines_or_keeps_values() {
+        let drops = DropCounter::new();
+
+        fn add<'a>(mut a: Droppy<'a, i32>, b: Droppy<'a, i32>) -> Droppy<'a, i32> {
+            a.val += b.val;
+            a
+        }
+
+        let both = CTSome::new(drops.droppy(40)).merge(CTSome::new(drops.droppy(2)), add);
+        assert!(drops.get() == 1);
+        assert!(both.unwrap_ref().val == 42);
+        drop(both);
+        assert!(drops.get() == 2);
+
+        let left = CTSome::new(drops.droppy(1)).merge(CTNone::new(), add);
+        let right = CTNone::new().merge(CTSome::new(drops.droppy(2)), add);
+        assert!(drops.get() == 2);
+        assert!(left.unwrap_ref().val == 1 && right.unwrap_ref().val == 2);
+        drop((left, right));
+        assert!(drops.get() == 4);
+
+        let none = CTNone::<Droppy<i32>>::new().merge(CTNone::new(), add);
+        assert!(!none.is_some());
+        drop(none);
+        assert!(drops.get() == 4);
+    }
+
+    #[cfg(feature = "generic_const_exprs")]
+    #[test]
+    fn zip3_and_zip4_combine_flags() {
+        let all = CTSome::new(1).zip3(CTSome::new('b'), CTSome::new("c"));
+        assert!(all.into_inner() == (1, 'b', "c"));
+
+        let partial = CTSome::new(1).zip3(CTNone::<char>::new(), CTSome::new("c"));
+        assert!(!partial.is_some());
+
+        let all = CTSome::new(1).zip4(CTSome::new(2), CTSome::new(3), CTSome::new(4));
+        assert!(all.into_inner() == (1, 2, 3, 4));
+
+        let partial = CTSome::new(1).zip4(CTSome::new(2), CTSome::new(3), CTNone::<i32>::new());
+        assert!(!partial.is_some());
+    }
+
+    #[cfg(feature = "generic_const_exprs")]
+    #[test]
+    fn flatten_ref_borrows_nested_value() {
+        let nested = CTSome::new(CTSome::new(42));
+        assert!(*nested.flatten_ref().into_inner() == 42);
+        assert!(nested.into_inner().into_inner() == 42);
+
+        let nested = CTSome::new(CTNone::<i32>::new());
+        assert!(!nested.flatten_ref().is_some());
+    }
+
+    #[cfg(feature = "generic_const_exprs")]
+    #[test]
+    fn ct_zip_all_zips_five_options() {
+        let all = ct_zip_all!(
+            CTSome::new(1),
+            CTSome::new(2u8),
+            CTSome::new('3'),
+            CTSome::new(4i64),
+            CTSome::new("5"),
+        );
+        assert!(all.into_inner() == (1, 2u8, '3', 4i64, "5"));
+
+        let partial = ct_zip_all!(
+            CTSome::new(1),
+            CTSome::new(2),
+            CTNone::<i32>::new(),
+            CTSome::new(4),
+            CTSome::new(5),
+        );
+        assert!(!partial.is_some());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn build_vec_collects_set_fields() {
+        let vec = build_vec!(
+            CTSome::new(1),
+            CTNone::new(),
+            CTSome::new(3),
+            CTNone::new(),
+            CTSome::new(5),
+        );
+        assert!(vec == [1, 3, 5]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn leak_returns_static_reference() {
+        use alloc::boxed::Box;
+
+        let leaked: &'static mut i32 = CTSome::new(Box::new(41)).leak();
+        *leaked += 1;
+        assert!(*leaked == 42);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn join_str_concatenates_set_fields() {
+        let joined = join_str!(
+            CTSome::new("type"),
+            CTNone::new(),
+            CTSome::new("state"),
+            CTNone::new(),
+        );
+        assert!(joined == "typestate");
+        assert!(join_str!().is_empty());
+    }
+
+    #[cfg(all(feature = "alloc", feature = "unsize"))]
+    #[test]
+    fn unsize_coerces_boxed_array_to_slice() {
+        use alloc::boxed::Box;
+
+        let slice: CTSome<Box<[u8]>> = CTSome::new(Box::new([1u8, 2, 3, 4])).unsize();
+        assert!(*slice.into_inner() == [1, 2, 3, 4]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn unbox_round_trips() {
+        use alloc::boxed::Box;
+
+        let some = CTSome::new(Box::new(42)).unbox();
+        assert!(some.into_inner() == 42);
+
+        let none = CTNone::<Box<i32>>::new().unbox();
+        assert!(!none.is_some());
+    }
+
+    #[cfg(feature = "const_trait_impl")]
+    macro_rules! provide_tests_guarded_by_const_trait_impl {
+        () => {
+            // See `provide_items_guarded_by_const_trait_impl` for the reason
+            // why the tests are in a macro.
+            #[test]
+            fn map_const_runs_in_const_context() {
+                struct AddOne;
+
+                impl const ConstFn<u8, u32> for AddOne {
+                    fn call(self, val: u8) -> u32 {
+                        val as u32 + 1
+                    }
+                }
+
+                const {
+                    let some = CTSome::new(41u8).map_const(AddOne);
+                    assert!(some.into_inner() == 42);
+
+                    let none = CTNone::<u8>::new().map_const(AddOne);
+                    assert!(!none.is_some());
+                    core::mem::forget(none);
+                }
+            }
+
+            #[test]
+            fn cleanup_drops_some_and_forgets_none() {
+                let drops = DropCounter::new();
+
+                const {
+                    CTSome::new(42).cleanup();
+                    CTNone::<i32>::new().cleanup();
+                }
+
+                CTSome::new(drops.droppy(())).cleanup();
+                assert!(drops.get() == 1);
+                CTNone::<Droppy>::new().cleanup();
+                assert!(drops.get() == 1);
+            }
+
+            #[test]
+            fn unwrap_or_else_const_computes_fallback() {
+                struct Answer;
+
+                impl const ConstFn<(), u32> for Answer {
+                    fn call(self, _: ()) -> u32 {
+                        6 * 7
+                    }
+                }
+
+                const {
+                    assert!(CTNone::<u32>::new().unwrap_or_else_const(Answer) == 42);
+                    assert!(CTSome::new(1).unwrap_or_else_const(Answer) == 1);
+                }
+            }
+
+            #[test]
+            fn const_eq_compares_in_const_context() {
+                const {
+                    let a = CTSome::new(42);
+                    let b = CTSome::new(42);
+                    let c = CTSome::new(0);
+                    assert!(a.const_eq(&b));
+                    assert!(!a.const_eq(&c));
+
+                    let none = CTNone::<i32>::new();
+                    assert!(none.const_eq(&CTNone::new()));
+                }
+            }
+
+            #[test]
+            fn widen_converts_in_const_context() {
+                const {
+                    let wide: CTSome<u32> = CTSome::new(200u8).widen();
+                    assert!(wide.into_inner() == 200);
+
+                    let none: CTNone<i64> = CTNone::<i8>::new().widen();
+                    assert!(!none.is_some());
+                    core::mem::forget(none);
+                }
+            }
+        };
+    }
+
+    #[test]
+    fn assume_some_ref_reads_through_the_alias() {
+        fn bump<const B: bool>(opt: &mut CTOption<i32, B>) -> i32 {
+            *unsafe { opt.assume_some_mut() }.unwrap_mut() += 1;
+            *unsafe { opt.assume_some_ref() }.unwrap_ref()
+        }
+
+        let mut opt = CTSome::new(11).into_some_tagged();
+        assert!(bump(&mut opt) == 12);
+        assert!(opt.into_inner() == 12);
+
+        let none = CTNone::<i32>::new();
+        assert!(!unsafe { none.assume_none_ref() }.is_some());
+    }
+
+    #[test]
+    fn map_or_else_ref_keeps_the_option() {
+        let some = CTSome::new(4);
+        assert!(some.map_or_else_ref(|| 0, |x| x * 2) == 8);
+        assert!(some.into_inner() == 4);
+
+        let none = CTNone::<i32>::new();
+        assert!(none.map_or_else_ref(|| -1, |x| x * 2) == -1);
+        assert!(none.insert(1).into_inner() == 1);
+    }
+
+    #[test]
+    const fn as_option_bridges_both_variants() {
+        const {
+            let some = CTSome::new(3);
+            assert!(matches!(some.as_option(), Some(&3)));
+            assert!(some.into_inner() == 3);
+
+            let none = CTNone::<i32>::new();
+            assert!(none.as_option().is_none());
+            core::mem::forget(none);
+        }
+    }
+
+    #[test]
+    fn emplace_initializes_in_place() {
+        let some = unsafe {
+            CTNone::<[u8; 32]>::new().emplace(|storage| {
+                let ptr = storage.as_mut_ptr() as *mut u8;
+                for i in 0..32 {
+                    ptr.add(i).write(i as u8);
+                }
+            })
+        };
+        let bytes = some.into_inner();
+        assert!(bytes.iter().enumerate().all(|(i, b)| *b == i as u8));
+    }
+
+    #[test]
+    const fn insert_mapped_changes_the_payload_type() {
+        const {
+            let some: CTSome<&str> = CTNone::<u32>::new().insert_mapped("set");
+            assert!(some.into_inner().len() == 3);
+        }
+    }
+
+    #[test]
+    fn try_insert_validates_before_committing() {
+        let drops = DropCounter::new();
+
+        fn non_zero(val: &Droppy<u8>) -> Result<(), &'static str> {
+            if val.val == 0 {
+                Err("the value must be non-zero")
+            } else {
+                Ok(())
+            }
+        }
+
+        let some = CTNone::new().try_insert(drops.droppy(1), non_zero);
+        assert!(drops.get() == 0);
+        assert!(some.is_ok_and(|some| some.unwrap_ref().val == 1));
+        assert!(drops.get() == 1);
+
+        let Err((none, err)) = CTNone::new().try_insert(drops.droppy(0), non_zero) else {
+            panic!("expected the validation to fail");
+        };
+        assert!(err == "the value must be non-zero");
+        assert!(drops.get() == 2);
+        drop(none);
+        assert!(drops.get() == 2);
+    }
+
+    #[cfg(feature = "generic_const_exprs")]
+    #[test]
+    fn project_converts_custom_implementors() {
+        #[repr(transparent)]
+        struct Port(CTSome<u16>);
+
+        unsafe impl OptionalConstGeneric for Port {
+            type Inner = u16;
+            const IS_SOME_VAL: bool = true;
+        }
+
+        let port: CTSome<u16> = project(Port(CTSome::new(8080)));
+        assert!(port.map(|port| port + 1).into_inner() == 8081);
+
+        let none: CTNone<i32> = project(CTNone::<i32>::new());
+        assert!(!none.is_some());
+    }
+
+    #[cfg(feature = "const_trait_impl")]
+    provide_tests_guarded_by_const_trait_impl!();
 }
@@ -0,0 +1,327 @@
+/// Zips several [`CTOption`](crate::CTOption)s into a single option of a flat tuple.
+///
+/// The resulting option is [`CTSome`](crate::CTSome) only if all the arguments are.
+/// The macro expands to nested [`CTOption::zip`](crate::CTOption::zip) calls and,
+/// therefore, requires the `generic_const_exprs` feature.
+///
+/// ```
+/// # #![allow(incomplete_features)]
+/// # #![feature(generic_const_exprs)]
+/// use ctoption::prelude::*;
+///
+/// let zipped = ct_zip_all!(CTSome::new(1), CTSome::new('a'), CTSome::new("b"));
+/// assert!(zipped.into_inner() == (1, 'a', "b"));
+/// ```
+#[cfg(feature = "generic_const_exprs")]
+#[macro_export]
+macro_rules! ct_zip_all {
+    ($first:expr $(, $rest:expr)+ $(,)?) => {
+        $crate::ct_zip_all!(@zip ($first) (first) (first) $($rest),+)
+    };
+    (@zip ($zipped:expr) ($pat:pat) ($($val:ident),+) $next:expr $(, $rest:expr)*) => {
+        $crate::ct_zip_all!(@zip ($zipped.zip($next)) (($pat, next)) ($($val,)+ next) $($rest),*)
+    };
+    (@zip ($zipped:expr) ($pat:pat) ($($val:ident),+)) => {
+        $zipped.map(|$pat| ($($val),+))
+    };
+}
+
+/// Collects the values of several [`CTOption`](crate::CTOption)s into a
+/// [`Vec`](alloc::vec::Vec), skipping the [`CTNone`](crate::CTNone)s.
+///
+/// Unlike an array, the resulting vector does not require the number of set
+/// options to be known at compile time.
+///
+/// ```
+/// use ctoption::prelude::*;
+///
+/// let vec = build_vec!(CTSome::new(1), CTNone::new(), CTSome::new(3));
+/// assert!(vec == [1, 3]);
+/// ```
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! build_vec {
+    ($($opt:expr),* $(,)?) => {{
+        let mut vec = $crate::__private::Vec::new();
+        $($crate::__private::push_into($opt, &mut vec);)*
+        vec
+    }};
+}
+
+/// Returns whether a [`CTOption`](crate::CTOption) is [`CTSome`](crate::CTSome).
+///
+/// ```
+/// use ctoption::prelude::*;
+///
+/// assert!(matches_some!(CTSome::new(42)));
+/// assert!(!matches_some!(CTNone::<i32>::new()));
+/// ```
+#[macro_export]
+macro_rules! matches_some {
+    ($opt:expr) => {
+        $crate::CTOption::is_some(&$opt)
+    };
+}
+
+/// Returns the flags of several [`CTOption`](crate::CTOption)s as a `[bool; N]`.
+///
+/// The options are only borrowed and each flag folds to a literal (see
+/// [`CTOption::is_some`](crate::CTOption::is_some)).
+///
+/// ```
+/// use ctoption::prelude::*;
+///
+/// let (a, b) = (CTSome::new(1), CTNone::<i32>::new());
+/// assert!(ct_flags!(a, b) == [true, false]);
+/// ```
+#[macro_export]
+macro_rules! ct_flags {
+    ($($opt:expr),* $(,)?) => {
+        [$($crate::CTOption::is_some(&$opt)),*]
+    };
+}
+
+/// Packs the flags of up to 32 [`CTOption`](crate::CTOption)s into a `u32`, where
+/// bit `i` is set if and only if the `i`-th option is [`CTSome`](crate::CTSome).
+///
+/// Like [`ct_flags!`], the options are only borrowed, so the macro can be used in `const fn`s.
+///
+/// ```
+/// use ctoption::prelude::*;
+///
+/// let (a, b, c) = (CTSome::new(1), CTNone::<i32>::new(), CTSome::new(3));
+/// assert!(ct_pack_flags!(a, b, c) == 0b101);
+/// ```
+#[macro_export]
+macro_rules! ct_pack_flags {
+    ($($opt:expr),* $(,)?) => {{
+        let flags: &[bool] = &$crate::ct_flags!($($opt),*);
+        assert!(flags.len() <= 32, "at most 32 flags can be packed into a `u32`");
+        let mut bits = 0u32;
+        let mut i = 0;
+        while i < flags.len() {
+            bits |= (flags[i] as u32) << i;
+            i += 1;
+        }
+        bits
+    }};
+}
+
+/// Matches a [`CTOption`](crate::CTOption) against its typestate.
+///
+/// A `match` expression can't tell [`CTSome`](crate::CTSome) and [`CTNone`](crate::CTNone)
+/// apart since they are different types. This macro branches on the compile-time flag instead,
+/// binding the value in the `CTSome` arm and forgetting the (empty) option in the `CTNone` arm,
+/// so the option is never dropped and the macro can be used in `const fn`s.
+///
+/// ```
+/// use ctoption::prelude::*;
+///
+/// const fn get_ty_alias_name<T: Copy, const IS_SOME_VAL: bool>(
+///     opt: CTOption<T, IS_SOME_VAL>,
+/// ) -> &'static str {
+///     ct_match!(opt,
+///         CTSome(_x) => "CTSome",
+///         CTNone => "CTNone",
+///     )
+/// }
+///
+/// const _: () = {
+///     assert!(matches!(get_ty_alias_name(CTSome::new(42)).as_bytes(), b"CTSome"));
+///     assert!(matches!(get_ty_alias_name(CTNone::<i32>::new()).as_bytes(), b"CTNone"));
+/// };
+/// ```
+#[macro_export]
+macro_rules! ct_match {
+    ($opt:expr, CTSome($val:pat) => $some:expr, CTNone => $none:expr $(,)?) => {{
+        let opt = $opt;
+        if $crate::CTOption::is_some(&opt) {
+            let $val = unsafe { $crate::CTOption::assume_some(opt) }.into_inner();
+            $some
+        } else {
+            ::core::mem::forget(opt);
+            $none
+        }
+    }};
+}
+
+/// Counts how many of the given option types are [`CTSome`](crate::CTSome).
+///
+/// The macro accepts types implementing [`OptionalConstGeneric`](crate::OptionalConstGeneric)
+/// and expands to a constant expression, so it can be used as an array length.
+///
+/// ```
+/// use ctoption::prelude::*;
+///
+/// let arr = [0; count_set!(CTSome<i32>, CTNone<i32>, CTSome<u8>)];
+/// assert!(arr.len() == 2);
+/// ```
+#[macro_export]
+macro_rules! count_set {
+    ($($opt:ty),* $(,)?) => {
+        0 $(+ (<$opt as $crate::OptionalConstGeneric>::IS_SOME_VAL as usize))*
+    };
+}
+
+/// Folds the values of several [`CTOption`](crate::CTOption)s into an accumulator,
+/// skipping the [`CTNone`](crate::CTNone)s.
+///
+/// The macro branches on the compile-time flag of each option (see [`ct_match!`])
+/// and can be used in `const fn`s when `f` is a `const fn`.
+///
+/// ```
+/// use ctoption::prelude::*;
+///
+/// const fn add(acc: i32, val: i32) -> i32 {
+///     acc + val
+/// }
+///
+/// const _: () = {
+///     let sum = ct_fold!(0, add, CTSome::new(1), CTNone::new(), CTSome::new(3));
+///     assert!(sum == 4);
+/// };
+/// ```
+#[macro_export]
+macro_rules! ct_fold {
+    ($init:expr, $f:expr $(, $opt:expr)* $(,)?) => {{
+        #[allow(unused_variables)]
+        let f = $f;
+        let acc = $init;
+        $(
+            let acc = $crate::ct_match!($opt,
+                CTSome(val) => f(acc, val),
+                CTNone => acc,
+            );
+        )*
+        acc
+    }};
+}
+
+/// Sums the values of several integer [`CTOption`](crate::CTOption)s, skipping the
+/// [`CTNone`](crate::CTNone)s.
+///
+/// Like [`ct_fold!`], the macro branches on the compile-time flag of each option,
+/// so it can be used in `const` contexts.
+///
+/// ```
+/// use ctoption::prelude::*;
+///
+/// const SUM: i32 = ct_sum!(CTSome::new(1), CTNone::<i32>::new(), CTSome::new(3));
+/// assert!(SUM == 4);
+/// ```
+#[macro_export]
+macro_rules! ct_sum {
+    ($($opt:expr),* $(,)?) => {{
+        let acc = 0;
+        $(
+            let acc = $crate::ct_match!($opt,
+                CTSome(val) => acc + val,
+                CTNone => acc,
+            );
+        )*
+        acc
+    }};
+}
+
+/// Multiplies the values of several integer [`CTOption`](crate::CTOption)s, skipping the
+/// [`CTNone`](crate::CTNone)s. See [`ct_sum!`].
+///
+/// ```
+/// use ctoption::prelude::*;
+///
+/// const PRODUCT: i32 = ct_product!(CTSome::new(2), CTNone::<i32>::new(), CTSome::new(21));
+/// assert!(PRODUCT == 42);
+/// ```
+#[macro_export]
+macro_rules! ct_product {
+    ($($opt:expr),* $(,)?) => {{
+        let acc = 1;
+        $(
+            let acc = $crate::ct_match!($opt,
+                CTSome(val) => acc * val,
+                CTNone => acc,
+            );
+        )*
+        acc
+    }};
+}
+
+/// Returns the first [`CTSome`](crate::CTSome) among several options of the same type,
+/// or a [`CTNone`](crate::CTNone) if there is none.
+///
+/// The resulting typestate is the OR of all the flags. The macro expands to chained
+/// [`CTOption::or`](crate::CTOption::or) calls and, therefore, requires the
+/// `generic_const_exprs` feature.
+///
+/// ```
+/// # #![allow(incomplete_features)]
+/// # #![feature(generic_const_exprs)]
+/// use ctoption::prelude::*;
+///
+/// let port = ct_or_all!(CTNone::new(), CTSome::new(8080), CTSome::new(80));
+/// assert!(port.into_inner() == 8080);
+/// ```
+#[cfg(feature = "generic_const_exprs")]
+#[macro_export]
+macro_rules! ct_or_all {
+    ($first:expr $(, $rest:expr)* $(,)?) => {
+        $first $(.or($rest))*
+    };
+}
+
+/// Concatenates the set ones among several `CTOption<&str, _>`s into a
+/// [`String`](alloc::string::String), preserving the order.
+///
+/// ```
+/// use ctoption::prelude::*;
+///
+/// let greeting = join_str!(CTSome::new("Hello"), CTNone::new(), CTSome::new(", world!"));
+/// assert!(greeting == "Hello, world!");
+/// ```
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! join_str {
+    ($($opt:expr),* $(,)?) => {
+        $crate::ct_fold!(
+            $crate::__private::String::new(),
+            |mut acc: $crate::__private::String, s: &str| {
+                acc.push_str(s);
+                acc
+            }
+            $(, $opt)*
+        )
+    };
+}
+
+/// Creates a [`CTOption`](crate::CTOption) from a const generic
+/// [`workarounds::Option`](crate::workarounds::Option) parameter, the reverse of
+/// [`CTOption::as_workaround`](crate::CTOption::as_workaround).
+///
+/// The flag of the option is `{ P.is_some() }` for the parameter `P`. This is a macro
+/// rather than a function because the type of a const parameter can't depend on
+/// a generic type.
+///
+/// ```
+/// #![allow(incomplete_features)]
+/// #![feature(adt_const_params, generic_const_exprs)]
+/// use ctoption::{prelude::*, workarounds::Option};
+///
+/// fn ct<const P: Option<u32>>() -> CTOption<u32, { P.is_some() }> {
+///     from_workaround!(P)
+/// }
+///
+/// assert!(ct::<{ Option::Some(42) }>().into_inner() == 42);
+/// ```
+#[cfg(all(feature = "adt_const_params", feature = "generic_const_exprs"))]
+#[macro_export]
+macro_rules! from_workaround {
+    ($p:expr) => {{
+        let storage = match $p {
+            $crate::workarounds::Option::Some(val) => ::core::mem::MaybeUninit::new(val),
+            $crate::workarounds::Option::None => ::core::mem::MaybeUninit::uninit(),
+        };
+        // SAFETY: the flag `{ $p.is_some() }` is `true` exactly when `$p` is `Some`,
+        // i.e. when `storage` was initialized by the first arm of the `match`.
+        unsafe { $crate::CTOption::<_, { $p.is_some() }>::from_maybe_uninit(storage) }
+    }};
+}
@@ -1,3 +1,16 @@
+pub use crate::erased::CtOptionDyn;
+pub use crate::flags::{and_flags, flag, not_flag, or_flags, xor_flags};
+#[cfg(all(feature = "adt_const_params", feature = "generic_const_exprs"))]
+pub use crate::from_workaround;
+#[cfg(feature = "alloc")]
+pub use crate::{build_vec, join_str};
 #[cfg(feature = "const_trait_impl")]
-pub use crate::const_drop;
-pub use crate::{CTNone, CTOption, CTSome, OptionalConstGeneric, IS_NONE, IS_SOME};
+pub use crate::{const_drop, ConstFn, ConstFrom, ConstPartialEq};
+pub use crate::{
+    count_set, ct_flags, ct_fold, ct_match, ct_pack_flags, ct_product, ct_sum, matches_some,
+};
+#[cfg(feature = "generic_const_exprs")]
+pub use crate::{ct_or_all, ct_zip_all, project};
+pub use crate::{
+    flatten_result, some_if, CTNone, CTOption, CTSome, OptionalConstGeneric, IS_NONE, IS_SOME,
+};
@@ -0,0 +1,34 @@
+//! Helpers shared by the unit tests of the crate.
+
+use core::cell::Cell;
+
+/// Counts how many of the [`Droppy`]s created with it have been dropped.
+pub(crate) struct DropCounter(Cell<usize>);
+
+impl DropCounter {
+    pub(crate) const fn new() -> Self {
+        Self(Cell::new(0))
+    }
+
+    /// Wraps `val` into a [`Droppy`] that increments the counter when dropped.
+    pub(crate) fn droppy<T>(&self, val: T) -> Droppy<'_, T> {
+        Droppy { counter: self, val }
+    }
+
+    /// Returns the number of drops so far.
+    pub(crate) fn get(&self) -> usize {
+        self.0.get()
+    }
+}
+
+/// A value whose drops are counted by a [`DropCounter`].
+pub(crate) struct Droppy<'a, T = ()> {
+    counter: &'a DropCounter,
+    pub(crate) val: T,
+}
+
+impl<T> Drop for Droppy<'_, T> {
+    fn drop(&mut self) {
+        self.counter.0.set(self.counter.0.get() + 1);
+    }
+}
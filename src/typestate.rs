@@ -0,0 +1,115 @@
+//! Type-level proofs of the typestate of a [`CTOption`](crate::CTOption).
+//!
+//! The traits allow generic code to require a particular typestate with a bound
+//! instead of a const generic parameter and to use it without `unsafe`.
+//! They are sealed, so they can't be implemented outside of this crate.
+//!
+//! ```
+//! use ctoption::{prelude::*, typestate::SomeState};
+//!
+//! fn extract<O: SomeState>(opt: O) -> O::Inner {
+//!     opt.unwrap_some()
+//! }
+//!
+//! assert!(extract(CTSome::new(42)) == 42);
+//! ```
+//!
+//! ```compile_fail
+//! use ctoption::{prelude::*, typestate::SomeState};
+//!
+//! fn extract<O: SomeState>(opt: O) -> O::Inner {
+//!     opt.unwrap_some()
+//! }
+//!
+//! extract(CTNone::<i32>::new());
+//! ```
+
+use crate::{CTNone, CTSome};
+
+/// A zero-sized proof that an option is [`CTSome`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IsSome;
+
+/// A zero-sized proof that an option is [`CTNone`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IsNone;
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl<T, const IS_SOME_VAL: bool> Sealed for crate::CTOption<T, IS_SOME_VAL> {}
+}
+
+/// Implemented only for [`CTSome`].
+pub trait SomeState: sealed::Sealed {
+    type Inner;
+
+    /// Moves the value out of the option.
+    fn unwrap_some(self) -> Self::Inner;
+}
+
+/// Implemented only for [`CTNone`].
+pub trait NoneState: sealed::Sealed {
+    type Inner;
+
+    /// Inserts a value into the option, turning it into a [`CTSome`].
+    fn insert(self, val: Self::Inner) -> CTSome<Self::Inner>;
+}
+
+impl<T> SomeState for CTSome<T> {
+    type Inner = T;
+
+    fn unwrap_some(self) -> T {
+        self.into_inner()
+    }
+}
+
+impl<T> NoneState for CTNone<T> {
+    type Inner = T;
+
+    fn insert(self, val: T) -> CTSome<T> {
+        CTNone::insert(self, val)
+    }
+}
+
+impl<T> CTSome<T> {
+    /// Returns the proof that the option is [`CTSome`].
+    pub const fn split_flag(&self) -> IsSome {
+        IsSome
+    }
+}
+
+impl<T> CTNone<T> {
+    /// Returns the proof that the option is [`CTNone`].
+    pub const fn split_flag(&self) -> IsNone {
+        IsNone
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proofs_match_typestate() {
+        fn requires_some<O: SomeState>(_opt: &O) {}
+        fn requires_none<O: NoneState>(_opt: &O) {}
+
+        let some = CTSome::new(42);
+        requires_some(&some);
+        assert!(some.split_flag() == IsSome);
+
+        let none = CTNone::<i32>::new();
+        requires_none(&none);
+        assert!(none.split_flag() == IsNone);
+    }
+
+    #[test]
+    fn bounded_generics_use_typestate() {
+        fn set_and_get<N: NoneState>(none: N, val: N::Inner) -> N::Inner {
+            NoneState::insert(none, val).unwrap_some()
+        }
+
+        assert!(set_and_get(CTNone::new(), 42) == 42);
+    }
+}
@@ -5,7 +5,10 @@
 #![feature(generic_const_exprs)]
 
 mod builder {
-    use ctoption::prelude::*;
+    use ctoption::{
+        array::{to_array, with_capacity_markers},
+        prelude::*,
+    };
 
     pub(super) struct Builder<
         const B0: bool,
@@ -23,12 +26,13 @@ mod builder {
 
     impl Builder<false, false, false, false, false> {
         pub(super) fn new() -> Self {
+            let (field0, field1, field2, field3, field4) = with_capacity_markers();
             Self {
-                field0: CTNone::new(),
-                field1: CTNone::new(),
-                field2: CTNone::new(),
-                field3: CTNone::new(),
-                field4: CTNone::new(),
+                field0,
+                field1,
+                field2,
+                field3,
+                field4,
             }
         }
     }
@@ -106,14 +110,15 @@ mod builder {
     impl<const B0: bool, const B1: bool, const B2: bool, const B3: bool, const B4: bool>
         Builder<B0, B1, B2, B3, B4>
     {
-        const LEN: usize =
-            { (B0 as usize) + (B1 as usize) + (B2 as usize) + (B3 as usize) + (B4 as usize) };
+        const LEN: usize = count_set!(
+            CTOption<i32, B0>,
+            CTOption<i32, B1>,
+            CTOption<i32, B2>,
+            CTOption<i32, B3>,
+            CTOption<i32, B4>,
+        );
 
         pub(super) fn build(self) -> [i32; Self::LEN] {
-            let mut arr = [0; Self::LEN];
-
-            let mut i = 0;
-
             let Self {
                 field0,
                 field1,
@@ -122,32 +127,13 @@ mod builder {
                 field4,
             } = self;
 
-            if B0 {
-                arr[i] = unsafe { field0.assume_some() }.into_inner();
-                i += 1;
-            }
-
-            if B1 {
-                arr[i] = unsafe { field1.assume_some() }.into_inner();
-                i += 1;
-            }
-
-            if B2 {
-                arr[i] = unsafe { field2.assume_some() }.into_inner();
-                i += 1;
-            }
-
-            if B3 {
-                arr[i] = unsafe { field3.assume_some() }.into_inner();
-                i += 1;
-            }
-
-            if B4 {
-                arr[i] = unsafe { field4.assume_some() }.into_inner();
-                // i += 1;
-            }
-
-            arr
+            to_array([
+                field0.tag_erase(),
+                field1.tag_erase(),
+                field2.tag_erase(),
+                field3.tag_erase(),
+                field4.tag_erase(),
+            ])
         }
     }
 }
@@ -0,0 +1,29 @@
+// The test replaces the process-wide panic hook, so it lives in its own test
+// binary to not interfere with the panics of other tests.
+
+#![cfg(debug_assertions)]
+
+use std::{
+    panic,
+    sync::{Arc, Mutex},
+};
+
+use ctoption::prelude::*;
+
+#[test]
+fn get_unchecked_reports_caller_location() {
+    let location = Arc::new(Mutex::new(None));
+
+    let hook = panic::take_hook();
+    let recorded = Arc::clone(&location);
+    panic::set_hook(Box::new(move |info| {
+        *recorded.lock().unwrap() = info.location().map(|l| (l.file().to_owned(), l.line()));
+    }));
+    let none = CTNone::<i32>::new();
+    let line = line!() + 1;
+    let res = panic::catch_unwind(|| unsafe { *none.get_unchecked() });
+    panic::set_hook(hook);
+
+    assert!(res.is_err());
+    assert!(*location.lock().unwrap() == Some((file!().to_owned(), line)));
+}